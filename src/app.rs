@@ -0,0 +1,311 @@
+use crate::{
+    error::Result,
+    renderer::{
+        buffer::VecBuffer,
+        input::InputEvent,
+        terminal_writer::{install_panic_hook, install_signal_handlers, TerminalWriter},
+        BackgroundColor, ForegroundColor, Simble, TextAttributes,
+    },
+};
+use crossterm::{
+    event::{
+        self, DisableBracketedPaste, DisableFocusChange, EnableBracketedPaste, EnableFocusChange,
+        Event, KeyEvent, MouseEvent,
+    },
+    terminal::size,
+    ExecutableCommand,
+};
+use std::{
+    io::{stdout, Stdout, Write},
+    time::{Duration, Instant},
+};
+
+//the writer owned by an app
+pub type Terminal<W = Stdout> = TerminalWriter<
+    VecBuffer<BackgroundColor>,
+    VecBuffer<ForegroundColor>,
+    VecBuffer<Simble>,
+    VecBuffer<TextAttributes>,
+    W,
+>;
+//events dispatched to the update function of an app
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AppEvent {
+    Key(KeyEvent),
+    Mouse(MouseEvent),
+    //the writer is resized before the event is dispatched
+    Resize(u16, u16),
+    FocusGained,
+    FocusLost,
+    Paste(String),
+    //sent once per tick rate
+    Tick,
+}
+impl From<Event> for AppEvent {
+    fn from(event: Event) -> Self {
+        match event {
+            Event::Key(key) => AppEvent::Key(key),
+            Event::Mouse(mouse) => AppEvent::Mouse(mouse),
+            Event::Resize(width, height) => AppEvent::Resize(width, height),
+            Event::FocusGained => AppEvent::FocusGained,
+            Event::FocusLost => AppEvent::FocusLost,
+            Event::Paste(text) => AppEvent::Paste(text),
+        }
+    }
+}
+impl AppEvent {
+    //the event for the focused widget, mouse events go through a hit test instead
+    pub fn input(&self) -> Option<InputEvent> {
+        match self {
+            AppEvent::Key(key) => Some(InputEvent::Key(*key)),
+            AppEvent::Paste(text) => Some(InputEvent::Paste(text.clone())),
+            _ => None,
+        }
+    }
+}
+//what the update function wants done after an event
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Control {
+    //nothing visible changed
+    #[default]
+    Continue,
+    //draw a new frame before waiting for the next event
+    Redraw,
+    Quit,
+}
+//owns the writer and the state of the application, reads terminal events and only
+//draws a frame when an event changed something
+pub struct App<S, W: Write = Stdout> {
+    terminal: Terminal<W>,
+    state: S,
+    tick_rate: Option<Duration>,
+    mouse_capture: bool,
+    bracketed_paste: bool,
+    focus_change: bool,
+    //a frame has to be drawn
+    dirty: bool,
+}
+impl<S> App<S> {
+    //an app drawing to stdout, sized like the terminal
+    pub fn new(state: S) -> Result<Self> {
+        let (width, height) = size()?;
+        Ok(Self::with_terminal(
+            Terminal::new_terminal(width, height, stdout()),
+            state,
+        ))
+    }
+}
+impl<S, W: Write> App<S, W> {
+    pub fn with_terminal(terminal: Terminal<W>, state: S) -> Self {
+        Self {
+            terminal,
+            state,
+            tick_rate: None,
+            mouse_capture: false,
+            bracketed_paste: false,
+            focus_change: false,
+            dirty: true,
+        }
+    }
+    //sends a Tick event at this rate, without one the app waits for events forever
+    pub fn tick_rate(mut self, tick_rate: Duration) -> Self {
+        self.tick_rate = Some(tick_rate);
+        self
+    }
+    pub fn mouse_capture(mut self, enabled: bool) -> Self {
+        self.mouse_capture = enabled;
+        self
+    }
+    pub fn bracketed_paste(mut self, enabled: bool) -> Self {
+        self.bracketed_paste = enabled;
+        self
+    }
+    pub fn focus_change(mut self, enabled: bool) -> Self {
+        self.focus_change = enabled;
+        self
+    }
+    pub fn state(&self) -> &S {
+        &self.state
+    }
+    pub fn state_mut(&mut self) -> &mut S {
+        &mut self.state
+    }
+    pub fn into_state(self) -> S {
+        self.state
+    }
+    pub fn terminal(&self) -> &Terminal<W> {
+        &self.terminal
+    }
+    pub fn terminal_mut(&mut self) -> &mut Terminal<W> {
+        &mut self.terminal
+    }
+    //the next call to draw renders a frame even if no event asked for it
+    pub fn request_redraw(&mut self) {
+        self.dirty = true;
+    }
+    //hands an event to the update function, resizes come with a redraw whatever it answers
+    pub fn dispatch(
+        &mut self,
+        event: AppEvent,
+        update: &mut impl FnMut(&mut S, AppEvent, &Terminal<W>) -> Control,
+    ) -> Result<Control> {
+        if let AppEvent::Resize(width, height) = event {
+            self.terminal.resize(width, height)?;
+            self.dirty = true;
+        }
+        let control = update(&mut self.state, event, &self.terminal);
+        if control == Control::Redraw {
+            self.dirty = true;
+        }
+        Ok(control)
+    }
+    //renders and flushes a frame if something changed since the last one
+    pub fn draw(
+        &mut self,
+        draw: &mut impl FnMut(&mut S, &mut Terminal<W>) -> Result<()>,
+    ) -> Result<bool> {
+        if !std::mem::take(&mut self.dirty) {
+            return Ok(false);
+        }
+        draw(&mut self.state, &mut self.terminal)?;
+        self.terminal.flush_frame()?;
+        Ok(true)
+    }
+    //takes the terminal and runs until update returns Quit, the terminal is given back
+    //when the app is dropped
+    pub fn run(
+        &mut self,
+        mut update: impl FnMut(&mut S, AppEvent, &Terminal<W>) -> Control,
+        mut draw: impl FnMut(&mut S, &mut Terminal<W>) -> Result<()>,
+    ) -> Result<()> {
+        install_panic_hook();
+        install_signal_handlers()?;
+        self.terminal.set_mouse_capture(self.mouse_capture)?;
+        self.terminal.prepare_area()?;
+        self.set_terminal_modes(true)?;
+        let result = self.event_loop(&mut update, &mut draw);
+        //the modes are restored even if the loop failed, its error is the one reported
+        let restored = self.set_terminal_modes(false);
+        result.and(restored)
+    }
+    fn event_loop(
+        &mut self,
+        update: &mut impl FnMut(&mut S, AppEvent, &Terminal<W>) -> Control,
+        draw: &mut impl FnMut(&mut S, &mut Terminal<W>) -> Result<()>,
+    ) -> Result<()> {
+        let mut last_tick = Instant::now();
+        loop {
+            self.draw(draw)?;
+            let timeout = self
+                .tick_rate
+                .map(|rate| rate.saturating_sub(last_tick.elapsed()));
+            //events that are already waiting are handled before the next frame
+            let mut ready = match timeout {
+                Some(timeout) => event::poll(timeout)?,
+                None => true,
+            };
+            while ready {
+                if self.dispatch(event::read()?.into(), update)? == Control::Quit {
+                    return Ok(());
+                }
+                ready = event::poll(Duration::ZERO)?;
+            }
+            if let Some(rate) = self.tick_rate {
+                if last_tick.elapsed() >= rate {
+                    last_tick = Instant::now();
+                    if self.dispatch(AppEvent::Tick, update)? == Control::Quit {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+    //bracketed paste and focus reports are only asked to a real terminal
+    fn set_terminal_modes(&mut self, enabled: bool) -> Result<()> {
+        if !self.terminal.is_tty() {
+            return Ok(());
+        }
+        let stream = self.terminal.stream_mut();
+        if self.bracketed_paste && enabled {
+            stream.execute(EnableBracketedPaste)?;
+        } else if self.bracketed_paste {
+            stream.execute(DisableBracketedPaste)?;
+        }
+        if self.focus_change && enabled {
+            stream.execute(EnableFocusChange)?;
+        } else if self.focus_change {
+            stream.execute(DisableFocusChange)?;
+        }
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{painter::Painter, painter::TextPainer, rect::Rect};
+    use crossterm::event::{KeyCode, KeyModifiers};
+
+    fn app() -> App<u32, Vec<u8>> {
+        App::with_terminal(Terminal::new(4, 2, Vec::new()), 0)
+    }
+    fn update(count: &mut u32, event: AppEvent, _: &Terminal<Vec<u8>>) -> Control {
+        match event {
+            AppEvent::Key(KeyEvent {
+                code: KeyCode::Char('q'),
+                ..
+            }) => Control::Quit,
+            AppEvent::Key(_) => {
+                *count += 1;
+                Control::Redraw
+            }
+            _ => Control::Continue,
+        }
+    }
+    fn key(c: char) -> AppEvent {
+        AppEvent::Key(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_redraws_only_on_change() {
+        let mut app = app();
+        let mut frames = 0;
+        let mut draw = |count: &mut u32, terminal: &mut Terminal<Vec<u8>>| {
+            frames += 1;
+            terminal.write_text_line(&count.to_string(), 0, 0);
+            Ok(())
+        };
+        assert!(app.draw(&mut draw).unwrap());
+        assert!(!app.draw(&mut draw).unwrap());
+        assert_eq!(
+            app.dispatch(AppEvent::Tick, &mut update).unwrap(),
+            Control::Continue
+        );
+        assert!(!app.draw(&mut draw).unwrap());
+        assert_eq!(
+            app.dispatch(key('a'), &mut update).unwrap(),
+            Control::Redraw
+        );
+        assert!(app.draw(&mut draw).unwrap());
+        assert_eq!(app.dispatch(key('q'), &mut update).unwrap(), Control::Quit);
+        assert_eq!(frames, 2);
+        assert_eq!(*app.state(), 1);
+    }
+
+    #[test]
+    fn test_resize_event() {
+        let mut app = app();
+        app.draw(&mut |_, _| Ok(())).unwrap();
+        app.dispatch(AppEvent::Resize(6, 3), &mut update).unwrap();
+        assert_eq!(app.terminal().area(), Rect::new(0, 0, 6, 3));
+        assert!(app.draw(&mut |_, _| Ok(())).unwrap());
+    }
+
+    #[test]
+    fn test_from_event() {
+        assert_eq!(AppEvent::from(Event::Resize(2, 3)), AppEvent::Resize(2, 3));
+        assert_eq!(
+            AppEvent::from(Event::Paste("text".to_owned())),
+            AppEvent::Paste("text".to_owned())
+        );
+    }
+}
@@ -1,37 +1,56 @@
 use std::io::stdout;
 
+use crate::app::AppEvent;
 use crate::renderer::{
     buffer::VecBuffer,
     buffer_mediator::BufferMediator,
-    core_widgetes::{border::Border, scrollbar::Scrollbar},
+    core_widgetes::{
+        border::{Border, BorderSymbols},
+        scrollbar::Scrollbar,
+    },
+    layout::flex::Flex,
     painter::Painter,
     rect::Rect,
-    terminal_writer::TerminalWriter,
+    terminal_writer::{
+        install_panic_hook, install_signal_handlers, SynchronizedOutput, TerminalWriter,
+    },
     widget::Widget,
     BackgroundColor, ForegroundColor, Simble,
 };
 use bevy::{
     app::{App, Plugin, PostUpdate, PreUpdate, Startup},
-    prelude::{Component, IntoSystemConfigs, Query, ResMut, Resource, SystemSet},
+    prelude::{
+        Component, Event, EventWriter, IntoSystem, IntoSystemConfigs, Query, Res, ResMut, Resource,
+        SystemSet,
+    },
     text::Text,
+    utils::error,
 };
-use compact_str::ToCompactString;
-use crossterm::terminal::size;
-pub struct SmallTuiPlugin;
+use crossterm::{event, terminal::size};
+use std::time::Duration;
+#[derive(Default)]
+pub struct SmallTuiPlugin {
+    pub synchronized_output: SynchronizedOutput,
+}
 
 impl Plugin for SmallTuiPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(SmalltuiTerminal(TerminalWriter::new(30, 30, stdout())))
+        let mut writer = TerminalWriter::new_terminal(30, 30, stdout());
+        writer.set_synchronized_output(self.synchronized_output);
+        app.insert_resource(SmalltuiTerminal(writer))
             .insert_resource(RenderQueue(Vec::new()))
             .insert_resource(ScreenSize {
                 width: 5,
                 height: 5,
             })
-            .add_systems(Startup, setup)
-            .add_systems(PreUpdate, resize_screen)
+            .add_systems(Startup, setup.map(error))
+            .add_event::<TerminalEvent>()
+            .add_systems(PreUpdate, read_terminal_events.map(error))
             .add_systems(
                 PostUpdate,
-                render_all.in_set(TuiRenerStages::ExcecuteRenders),
+                render_all
+                    .map(error)
+                    .in_set(TuiRenerStages::ExcecuteRenders),
             );
     }
 }
@@ -76,7 +95,10 @@ pub struct ComputedUINode {
     scroll_y: u16,
 }
 
-pub fn render_all(mut queue: ResMut<RenderQueue>, mut writer: ResMut<SmalltuiTerminal>) {
+pub fn render_all(
+    mut queue: ResMut<RenderQueue>,
+    mut writer: ResMut<SmalltuiTerminal>,
+) -> crate::Result<()> {
     let requests = &mut queue.0;
     let writer = &mut writer.as_mut().0;
     writer.background_fill(crossterm::style::Color::Cyan.into(), None);
@@ -85,27 +107,22 @@ pub fn render_all(mut queue: ResMut<RenderQueue>, mut writer: ResMut<SmalltuiTer
         let widget = r.widget.as_ref();
         writer.render_widget(widget, r.area, r.scroll_x, r.scroll_y);
     }
-    writer.flush_frame().unwrap();
+    writer.flush_frame()
 }
 
-pub fn queue_renders(mut queue: ResMut<RenderQueue>, /* , q: Query<(&ComputedUINode, &Text)>*/) {
+pub fn queue_renders(
+    mut queue: ResMut<RenderQueue>,
+    sc: Res<ScreenSize>, /* , q: Query<(&ComputedUINode, &Text)>*/
+) {
     //for (area, text) in &q {
-    for i in 0..18 {
+    let screen = Rect::new(0, 0, sc.width, sc.height);
+    let tiles = Flex::horizontal().wrap(true);
+    let areas = tiles.split(screen, &[tiles.item(8, 5); 18]);
+    for (i, area) in (0..).zip(areas) {
         queue.0.push(RenderRequest {
-            widget: Box::new(Border::new(
-                '#'.to_compact_string().into(),
-                '#'.to_compact_string().into(),
-                '|'.to_compact_string().into(),
-                '|'.to_compact_string().into(),
-                '-'.to_compact_string().into(),
-                '-'.to_compact_string().into(),
-                '#'.to_compact_string().into(),
-                '#'.to_compact_string().into(),
-                8,
-                5,
-            )),
+            widget: Box::new(Border::new().symbols(BorderSymbols::ASCII)),
             depth: 0,
-            area: Rect::new(8 * (i * 6), 5 * (i / 6), 8, 5),
+            area,
             scroll_x: i,
             scroll_y: i,
         });
@@ -116,14 +133,38 @@ pub struct ScreenSize {
     pub width: u16,
     pub height: u16,
 }
-pub fn resize_screen(mut writer: ResMut<SmalltuiTerminal>, mut sc: ResMut<ScreenSize>) {
-    let (current_width, current_height) = size().unwrap();
-    if (current_width, current_height) != (sc.width, sc.height) {
-        sc.width = current_width;
-        sc.height = current_height;
-        writer.0.resize(current_width, current_height);
+//terminal events read during the frame, resizes are applied to the writer before they are sent
+#[derive(Event, Clone, Debug, PartialEq, Eq)]
+pub struct TerminalEvent(pub AppEvent);
+//reads the events waiting without blocking the frame
+pub fn read_terminal_events(
+    mut writer: ResMut<SmalltuiTerminal>,
+    mut sc: ResMut<ScreenSize>,
+    mut events: EventWriter<TerminalEvent>,
+) -> crate::Result<()> {
+    while event::poll(Duration::ZERO)? {
+        let event = AppEvent::from(event::read()?);
+        if let AppEvent::Resize(width, height) = event {
+            if (width, height) != (sc.width, sc.height) {
+                sc.width = width;
+                sc.height = height;
+                writer.0.resize(width, height)?;
+            }
+        }
+        events.send(TerminalEvent(event));
     }
+    Ok(())
 }
-pub fn setup(mut writer: ResMut<SmalltuiTerminal>) {
-    writer.0.prepare_area();
+pub fn setup(
+    mut writer: ResMut<SmalltuiTerminal>,
+    mut sc: ResMut<ScreenSize>,
+) -> crate::Result<()> {
+    install_panic_hook();
+    install_signal_handlers()?;
+    //later sizes come from resize events
+    let (width, height) = size()?;
+    sc.width = width;
+    sc.height = height;
+    writer.0.resize(width, height)?;
+    writer.0.prepare_area()
 }
@@ -0,0 +1,66 @@
+use std::{fmt, io};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    //writing to the terminal or changing its mode failed
+    Io(io::Error),
+    //a scrollbar needs room for its two arrows
+    ScrollbarTooShort {
+        lenght: u16,
+    },
+    //the grabbable part of a scrollbar doesn't fit inside the bar
+    GrabbableOutOfBounds {
+        lenght: u16,
+        grabbable_lenght: u16,
+        position: u16,
+    },
+}
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(error) => write!(f, "terminal io error: {}", error),
+            Error::ScrollbarTooShort { lenght } => write!(
+                f,
+                "scrollbars require at least a lenght of 2, got {}",
+                lenght
+            ),
+            Error::GrabbableOutOfBounds {
+                lenght,
+                grabbable_lenght,
+                position,
+            } => write!(
+                f,
+                "grabbable element of lenght {} at {} doesn't fit inside a bar of lenght {}",
+                grabbable_lenght, position, lenght
+            ),
+        }
+    }
+}
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error as _;
+
+    #[test]
+    fn test_from_io() {
+        let error: Error = io::Error::new(io::ErrorKind::BrokenPipe, "closed").into();
+        assert!(matches!(error, Error::Io(_)));
+        assert!(error.source().is_some());
+        assert_eq!(error.to_string(), "terminal io error: closed");
+    }
+}
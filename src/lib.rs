@@ -1,2 +1,5 @@
+pub mod app;
 pub mod bevy_plugin;
+pub mod error;
 pub mod renderer;
+pub use error::{Error, Result};
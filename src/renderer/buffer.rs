@@ -26,17 +26,18 @@ impl<T: Default + Sized + Clone + Send + Sync> Buffer<T> for VecBuffer<T> {
             data: vec![T::default(); width as usize * height as usize],
         }
     }
+    //the part of the line outside of the buffer is clipped
     fn draw_line(&mut self, data: &[T], x: u16, y: u16, lenght: u16) {
-        if lenght + x > self.width {
-            panic!("x overflow")
+        if x >= self.width || y >= self.height {
+            return;
         }
-        if y >= self.height {
-            panic!("y overflow")
-        }
-        let start = (y * self.width + x) as usize;
+        let lenght = (lenght as usize)
+            .min((self.width - x) as usize)
+            .min(data.len());
+        let start = y as usize * self.width as usize + x as usize;
 
-        let buffer_section = &mut self.data[start..start + lenght as usize];
-        buffer_section.clone_from_slice(&data[0..lenght as usize]);
+        let buffer_section = &mut self.data[start..start + lenght];
+        buffer_section.clone_from_slice(&data[0..lenght]);
     }
     fn get_mediator(&self, region: Option<Rect>) -> BufferMediator {
         let buffer_area = self.area();
@@ -96,17 +97,26 @@ mod tests {
     }
 
     #[test]
-    #[should_panic]
     fn test_draw_line_x_overflow() {
         let mut buffer: VecBuffer<u8> = VecBuffer::new(10, 20);
-        buffer.draw_line(&vec![1, 2, 3, 4, 5], 8, 5, 5);
+        buffer.draw_line(&[1, 2, 3, 4, 5], 8, 5, 5);
+        assert_eq!(&buffer.data[57..61], &[0, 1, 2, 0]);
+        buffer.draw_line(&[1, 2, 3, 4, 5], 10, 5, 5);
+        assert_eq!(&buffer.data[60..61], &[0]);
     }
 
     #[test]
-    #[should_panic]
     fn test_draw_line_y_overflow() {
         let mut buffer: VecBuffer<u8> = VecBuffer::new(10, 20);
-        buffer.draw_line(&vec![1, 2, 3, 4, 5], 2, 21, 5);
+        buffer.draw_line(&[1, 2, 3, 4, 5], 2, 21, 5);
+        assert_eq!(buffer.data, vec![0; 200]);
+    }
+
+    #[test]
+    fn test_draw_line_short_data() {
+        let mut buffer: VecBuffer<u8> = VecBuffer::new(10, 20);
+        buffer.draw_line(&[1, 2], 0, 0, 5);
+        assert_eq!(&buffer.data[0..3], &[1, 2, 0]);
     }
 
     #[test]
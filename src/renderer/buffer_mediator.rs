@@ -32,11 +32,17 @@ impl BufferMediator {
         for i in 0..visible.height as usize {
             let y_to_draw = i + first_y; //current row of the request being writtent
             let starting_index = y_to_draw * (area.width as usize) + first_x; //index of the first element of the row to write
+
+            //data shorter than the area leaves the rest of the area untouched
+            let Some(row) = data.get(starting_index..) else {
+                break;
+            };
+            let row = &row[..row.len().min(visible.width as usize)]; //slice of data to draw
             buffer.draw_line(
-                &data[starting_index..(starting_index + visible.width as usize)], //slice of data to draw
+                row,
                 screen_area.x,
                 screen_area.y + (i as u16),
-                visible.width,
+                row.len() as u16,
             );
         }
     }
@@ -75,6 +81,20 @@ impl BufferMediator {
             offset_y: offset_y,
         }
     }
+    //part of the screen the mediator writes to
+    pub fn screen_area(&self) -> Rect {
+        self.area
+    }
+    //position in the space of the widget of a point of the screen, scroll included
+    pub fn to_local(&self, x: u16, y: u16) -> Option<(u16, u16)> {
+        if !self.area.contains_point(x, y) {
+            return None;
+        }
+        Some((
+            x - self.area.x + self.offset_x,
+            y - self.area.y + self.offset_y,
+        ))
+    }
     pub fn size(&self) -> Rect {
         return Rect::new(0, 0, self.area.width, self.area.height);
     }
@@ -110,6 +130,15 @@ mod tests {
         assert_eq!(screen_space, Rect::new(5, 5, 50, 50));
     }
 
+    #[test]
+    fn test_to_local() {
+        let mediator = BufferMediator::new(Rect::new(5, 5, 10, 10), 2, 3);
+        assert_eq!(mediator.to_local(5, 5), Some((2, 3)));
+        assert_eq!(mediator.to_local(7, 9), Some((4, 7)));
+        assert_eq!(mediator.to_local(15, 5), None);
+        assert_eq!(mediator.to_local(4, 5), None);
+    }
+
     #[test]
     fn test_generate_inner() {
         let mediator = BufferMediator::new(Rect::new(0, 0, 100, 100), 10, 20);
@@ -150,4 +179,19 @@ mod tests {
             }
         }
     }
+    #[test]
+    fn write_short_data() {
+        let mediator = BufferMediator::new(Rect::new(0, 0, 4, 4), 0, 0);
+        let mut buffer: VecBuffer<u8> = VecBuffer::new(4, 4);
+        mediator.write(&[1, 2, 3], Rect::new(0, 0, 2, 2), &mut buffer);
+        assert_eq!(
+            (
+                buffer[(0, 0)],
+                buffer[(1, 0)],
+                buffer[(0, 1)],
+                buffer[(1, 1)]
+            ),
+            (1, 2, 3, 0)
+        );
+    }
 }
@@ -0,0 +1,135 @@
+use super::border::Border;
+use crate::renderer::{
+    layout::Padding, painter::Painter, rect::Rect, widget::Widget, BackgroundColor,
+};
+
+//a child widget drawn inside a border and padding, the child only sees the inner rect so
+//whatever it draws past it is clipped
+pub struct Block<T> {
+    child: T,
+    border: Option<Border>,
+    background: Option<BackgroundColor>,
+    padding: Padding,
+    scroll_x: u16,
+    scroll_y: u16,
+}
+impl<T> Block<T> {
+    //a single line border around the child, without padding
+    pub fn new(child: T) -> Self {
+        Self {
+            child,
+            border: Some(Border::new()),
+            background: None,
+            padding: Padding::default(),
+            scroll_x: 0,
+            scroll_y: 0,
+        }
+    }
+    //a border with a fixed size keeps it, the child stays inside it
+    pub fn border(mut self, border: Border) -> Self {
+        self.border = Some(border);
+        self
+    }
+    pub fn without_border(mut self) -> Self {
+        self.border = None;
+        self
+    }
+    //fills the whole block before the border and the child are drawn
+    pub fn background(mut self, color: impl Into<BackgroundColor>) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+    //space between the border and the child
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+    //scroll of the child inside the inner rect
+    pub fn scroll(mut self, scroll_x: u16, scroll_y: u16) -> Self {
+        self.scroll_x = scroll_x;
+        self.scroll_y = scroll_y;
+        self
+    }
+    pub fn child(&self) -> &T {
+        &self.child
+    }
+    pub fn child_mut(&mut self) -> &mut T {
+        &mut self.child
+    }
+    //the rect the child is rendered in for a block drawn over the area
+    pub fn inner(&self, area: Rect) -> Rect {
+        let area = match &self.border {
+            Some(border) => border.inner(border.outer(area)),
+            None => area,
+        };
+        area.inner(self.padding)
+    }
+}
+impl<P: Painter, T: Widget<P>> Widget<P> for Block<T> {
+    fn render_widget(&self, painter: &mut P) {
+        let area = painter.area();
+        if let Some(color) = self.background {
+            painter.background_fill(color, None);
+        }
+        if let Some(border) = &self.border {
+            painter.render_widget(border, area, 0, 0);
+        }
+        let inner = self.inner(area);
+        if inner.width > 0 && inner.height > 0 {
+            painter.render_widget(&self.child, inner, self.scroll_x, self.scroll_y);
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{
+        core_widgetes::border::BorderSymbols, painter::TextPainer, test_backend::TB,
+    };
+    use crossterm::style::Color;
+
+    struct Lines;
+    impl<P: Painter + TextPainer> Widget<P> for Lines {
+        fn render_widget(&self, painter: &mut P) {
+            for i in 0..4 {
+                painter.write_text_line(&format!("line{}", i), 0, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_child_is_clipped() {
+        let mut backend = TB::new(8, 5);
+        let block = Block::new(Lines)
+            .border(Border::new().symbols(BorderSymbols::ASCII))
+            .padding(Padding::horizontal(1));
+        backend.render_widget(&block, Rect::new(1, 0, 7, 4), 0, 0);
+        backend.assert_text(&[" +-----+", " | lin |", " | lin |", " +-----+", "        "]);
+        assert_eq!(block.inner(Rect::new(1, 0, 7, 4)), Rect::new(3, 1, 3, 2));
+    }
+
+    #[test]
+    fn test_child_stays_in_sized_border() {
+        let mut backend = TB::new(8, 5);
+        let block =
+            Block::new(Lines).border(Border::new().symbols(BorderSymbols::ASCII).size(5, 3));
+        backend.render_widget(&block, backend.area(), 0, 0);
+        backend.assert_text(&["+---+   ", "|lin|   ", "+---+   ", "        ", "        "]);
+        assert_eq!(block.inner(backend.area()), Rect::new(1, 1, 3, 1));
+    }
+
+    #[test]
+    fn test_child_scroll_and_background() {
+        let mut backend = TB::new(6, 2);
+        let block = Block::new(Lines)
+            .without_border()
+            .background(Color::Blue)
+            .padding(Padding::new(0, 0, 0, 1))
+            .scroll(2, 1);
+        backend.render_widget(&block, Rect::new(0, 0, 5, 2), 0, 0);
+        backend.assert_text(&[" ne1  ", " ne2  "]);
+        assert_eq!(backend.cell(0, 0).background, Color::Blue.into());
+        assert_eq!(backend.cell(4, 1).background, Color::Blue.into());
+        assert_eq!(backend.cell(5, 1).background, BackgroundColor::default());
+    }
+}
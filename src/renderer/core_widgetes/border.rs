@@ -1,58 +1,327 @@
-use crate::renderer::{painter::Painter, rect::Rect, widget::Widget, Simble};
+use crate::renderer::{
+    painter::{
+        paragraph::{Alignment, ParagraphLayout, Wrap},
+        Painter, TextPainer,
+    },
+    rect::Rect,
+    text::{Line, Style},
+    widget::Widget,
+    BoxSide, Simble,
+};
 
-pub struct Border {
-    top_left: Simble,
-    top_right: Simble,
-    vertical_left: Simble,
-    vertical_right: Simble,
-    top_line: Simble,
-    bottom_line: Simble,
-    bottom_left: Simble,
-    bottom_right: Simble,
-    width: u16,
-    height: u16,
+//the simbles a border is drawn with
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BorderSymbols {
+    pub top_left: Simble,
+    pub top_right: Simble,
+    pub vertical_left: Simble,
+    pub vertical_right: Simble,
+    pub top_line: Simble,
+    pub bottom_line: Simble,
+    pub bottom_left: Simble,
+    pub bottom_right: Simble,
 }
-
-impl Border {
-    pub fn new(
-        top_left: Simble,
-        top_right: Simble,
-        vertical_left: Simble,
-        vertical_right: Simble,
-        top_line: Simble,
-        bottom_line: Simble,
-        bottom_left: Simble,
-        bottom_right: Simble,
-        width: u16,
-        height: u16,
+impl BorderSymbols {
+    const fn preset(
+        corners: [&'static str; 4],
+        vertical: &'static str,
+        line: &'static str,
     ) -> Self {
         Self {
-            top_left,
-            top_right,
-            vertical_left,
-            vertical_right,
-            top_line,
-            bottom_line,
-            bottom_left,
-            bottom_right,
-            width,
-            height,
+            top_left: Simble::from_static(corners[0]),
+            top_right: Simble::from_static(corners[1]),
+            vertical_left: Simble::from_static(vertical),
+            vertical_right: Simble::from_static(vertical),
+            top_line: Simble::from_static(line),
+            bottom_line: Simble::from_static(line),
+            bottom_left: Simble::from_static(corners[2]),
+            bottom_right: Simble::from_static(corners[3]),
+        }
+    }
+    pub const ASCII: BorderSymbols = Self::preset(["+", "+", "+", "+"], "|", "-");
+    pub const SINGLE: BorderSymbols = Self::preset(["┌", "┐", "└", "┘"], "│", "─");
+    pub const DOUBLE: BorderSymbols = Self::preset(["╔", "╗", "╚", "╝"], "║", "═");
+    pub const ROUNDED: BorderSymbols = Self::preset(["╭", "╮", "╰", "╯"], "│", "─");
+    pub const THICK: BorderSymbols = Self::preset(["┏", "┓", "┗", "┛"], "┃", "━");
+    pub const DASHED: BorderSymbols = Self::preset(["┌", "┐", "└", "┘"], "╎", "╌");
+}
+impl Default for BorderSymbols {
+    fn default() -> Self {
+        Self::SINGLE
+    }
+}
+//a title written over the top or bottom line, between the corners
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Title {
+    line: Line,
+    alignment: Alignment,
+}
+//a box around the area it is rendered in, or of a fixed size
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Border {
+    symbols: BorderSymbols,
+    //top, bottom, left and right
+    sides: [bool; 4],
+    style: Style,
+    top_title: Option<Title>,
+    bottom_title: Option<Title>,
+    //None takes the size of the painter
+    width: Option<u16>,
+    height: Option<u16>,
+}
+impl Default for Border {
+    fn default() -> Self {
+        Self {
+            symbols: BorderSymbols::default(),
+            sides: [true; 4],
+            style: Style::new(),
+            top_title: None,
+            bottom_title: None,
+            width: None,
+            height: None,
         }
     }
 }
+impl Border {
+    //a single line border on every side, as big as the area it is rendered in
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn symbols(mut self, symbols: BorderSymbols) -> Self {
+        self.symbols = symbols;
+        self
+    }
+    //draws only the given sides, a corner needs both of its sides
+    pub fn sides(mut self, sides: &[BoxSide]) -> Self {
+        self.sides = [BoxSide::UP, BoxSide::Down, BoxSide::Left, BoxSide::Right]
+            .map(|side| sides.contains(&side));
+        self
+    }
+    pub fn side(mut self, side: BoxSide, enabled: bool) -> Self {
+        self.sides[Self::index(side)] = enabled;
+        self
+    }
+    pub fn has_side(&self, side: BoxSide) -> bool {
+        self.sides[Self::index(side)]
+    }
+    //colors and attributes of the lines, titles keep the style of their spans on top
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+    //only drawn when the top side is
+    pub fn title(mut self, title: impl Into<Line>, alignment: Alignment) -> Self {
+        self.top_title = Some(Title {
+            line: title.into(),
+            alignment,
+        });
+        self
+    }
+    //only drawn when the bottom side is
+    pub fn bottom_title(mut self, title: impl Into<Line>, alignment: Alignment) -> Self {
+        self.bottom_title = Some(Title {
+            line: title.into(),
+            alignment,
+        });
+        self
+    }
+    pub fn size(mut self, width: u16, height: u16) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+    //the rect the border is drawn in when rendered over the area, a fixed size is cut to it
+    pub fn outer(&self, area: Rect) -> Rect {
+        Rect::new(
+            area.x,
+            area.y,
+            self.width.map_or(area.width, |width| width.min(area.width)),
+            self.height
+                .map_or(area.height, |height| height.min(area.height)),
+        )
+    }
+    //the part of the area left inside the sides that are drawn
+    pub fn inner(&self, area: Rect) -> Rect {
+        let [top, bottom, left, right] = self.sides.map(u16::from);
+        Rect::new(
+            area.x.saturating_add(left),
+            area.y.saturating_add(top),
+            area.width.saturating_sub(left + right),
+            area.height.saturating_sub(top + bottom),
+        )
+    }
+    fn index(side: BoxSide) -> usize {
+        match side {
+            BoxSide::UP => 0,
+            BoxSide::Down => 1,
+            BoxSide::Left => 2,
+            BoxSide::Right => 3,
+        }
+    }
+    //a horizontal line, the corners are replaced by the line where their vertical side is off
+    fn line(&self, width: u16, line: &Simble, left: &Simble, right: &Simble) -> Vec<Simble> {
+        let [_, _, has_left, has_right] = self.sides;
+        let mut simbles = vec![line.clone(); width as usize];
+        if has_left {
+            simbles[0] = left.clone();
+        }
+        if has_right {
+            simbles[width as usize - 1] = right.clone();
+        }
+        simbles
+    }
+    fn write_title<P: Painter>(&self, painter: &mut P, title: &Option<Title>, y: u16, width: u16) {
+        let Some(title) = title else {
+            return;
+        };
+        let [_, _, left, right] = self.sides.map(u16::from);
+        let layout = ParagraphLayout::new()
+            .wrap(Wrap::None)
+            .alignment(title.alignment)
+            .ellipsis('…');
+        let area = Rect::new(left, y, width.saturating_sub(left + right), 1);
+        painter.write_text(&title.line.clone().into(), area, &layout);
+    }
+}
 impl<P: Painter> Widget<P> for Border {
     fn render_widget<'b>(&self, painter: &'b mut P) {
-        let mut line = vec![self.top_line.clone(); self.width as usize];
-        line[0] = self.top_left.clone();
-        line[self.width as usize - 1] = self.top_right.clone();
-        painter.write_simbles(&line, Rect::new(0, 0, self.width, 1));
-        line = vec![self.bottom_line.clone(); self.width as usize];
-        line[0] = self.bottom_left.clone();
-        line[self.width as usize - 1] = self.bottom_right.clone();
-        painter.write_simbles(&line, Rect::new(0, self.height - 1, self.width, 1));
-        line = vec![self.vertical_left.clone(); self.height as usize - 2];
-        painter.write_simbles(&line, Rect::new(0, 1, 1, self.height - 2));
-        line = vec![self.vertical_right.clone(); self.height as usize - 2];
-        painter.write_simbles(&line, Rect::new(self.width - 1, 1, 1, self.height - 2));
+        let area = painter.area();
+        let width = self.width.unwrap_or(area.width);
+        let height = self.height.unwrap_or(area.height);
+        let [top, bottom, left, right] = self.sides;
+        //a border too small for its sides has nothing sensible to draw
+        let (columns, rows) = (
+            u16::from(left) + u16::from(right),
+            u16::from(top) + u16::from(bottom),
+        );
+        if width == 0 || height == 0 || width < columns || height < rows {
+            return;
+        }
+        let symbols = &self.symbols;
+        let middle = Rect::new(0, u16::from(top), 1, height - rows);
+        if top {
+            let line = self.line(
+                width,
+                &symbols.top_line,
+                &symbols.top_left,
+                &symbols.top_right,
+            );
+            painter.write_simbles(&line, Rect::new(0, 0, width, 1));
+            self.style.fill(painter, Some(Rect::new(0, 0, width, 1)));
+            self.write_title(painter, &self.top_title, 0, width);
+        }
+        if bottom {
+            let line = self.line(
+                width,
+                &symbols.bottom_line,
+                &symbols.bottom_left,
+                &symbols.bottom_right,
+            );
+            let y = height - 1;
+            painter.write_simbles(&line, Rect::new(0, y, width, 1));
+            self.style.fill(painter, Some(Rect::new(0, y, width, 1)));
+            self.write_title(painter, &self.bottom_title, y, width);
+        }
+        if left && middle.height > 0 {
+            let line = vec![symbols.vertical_left.clone(); middle.height as usize];
+            painter.write_simbles(&line, middle);
+            self.style.fill(painter, Some(middle));
+        }
+        if right && middle.height > 0 {
+            let column = Rect::new(width - 1, middle.y, 1, middle.height);
+            let line = vec![symbols.vertical_right.clone(); middle.height as usize];
+            painter.write_simbles(&line, column);
+            self.style.fill(painter, Some(column));
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{test_backend::TB, ForegroundColor};
+    use crossterm::style::Color;
+
+    fn border(width: u16, height: u16) -> Border {
+        Border::new()
+            .symbols(BorderSymbols {
+                top_left: '1'.into(),
+                top_right: '2'.into(),
+                vertical_left: '|'.into(),
+                vertical_right: '!'.into(),
+                top_line: '-'.into(),
+                bottom_line: '_'.into(),
+                bottom_left: '3'.into(),
+                bottom_right: '4'.into(),
+            })
+            .size(width, height)
+    }
+
+    #[test]
+    fn test_render() {
+        let mut backend = TB::new(6, 4);
+        backend.render_widget(&border(5, 3), Rect::new(1, 1, 5, 3), 0, 0);
+        backend.assert_text(&["      ", " 1---2", " |   !", " 3___4"]);
+    }
+
+    #[test]
+    fn test_render_scrolled() {
+        let mut backend = TB::new(5, 3);
+        backend.render_widget(&border(5, 3), Rect::new(0, 0, 5, 3), 2, 1);
+        backend.assert_text(&["  !  ", "__4  ", "     "]);
+    }
+
+    #[test]
+    fn test_render_too_small() {
+        let mut backend = TB::new(3, 3);
+        backend.render_widget(&border(1, 3), Rect::new(0, 0, 3, 3), 0, 0);
+        backend.render_widget(&border(3, 0), Rect::new(0, 0, 3, 3), 0, 0);
+        backend.assert_text(&["   ", "   ", "   "]);
+    }
+
+    #[test]
+    fn test_render_off_screen() {
+        let mut backend = TB::new(4, 3);
+        backend.render_widget(&border(5, 3), Rect::new(2, 1, 5, 3), 0, 0);
+        backend.assert_text(&["    ", "  1-", "  | "]);
+    }
+
+    #[test]
+    fn test_sized_from_area() {
+        let mut backend = TB::new(5, 4);
+        let border = Border::new().symbols(BorderSymbols::ROUNDED);
+        backend.render_widget(&border, Rect::new(0, 0, 4, 3), 0, 0);
+        backend.assert_text(&["╭──╮ ", "│  │ ", "╰──╯ ", "     "]);
+    }
+
+    #[test]
+    fn test_partial_sides() {
+        let mut backend = TB::new(4, 3);
+        let border = Border::new()
+            .symbols(BorderSymbols::ASCII)
+            .sides(&[BoxSide::UP, BoxSide::Left]);
+        backend.render_widget(&border, backend.area(), 0, 0);
+        backend.assert_text(&["+---", "|   ", "|   "]);
+        assert_eq!(border.inner(backend.area()), Rect::new(1, 1, 3, 2));
+        let border = Border::new()
+            .symbols(BorderSymbols::ASCII)
+            .side(BoxSide::Left, false);
+        backend.reset();
+        backend.render_widget(&border, backend.area(), 0, 0);
+        backend.assert_text(&["---+", "   |", "---+"]);
+    }
+
+    #[test]
+    fn test_titles_and_style() {
+        let mut backend = TB::new(9, 3);
+        let border = Border::new()
+            .symbols(BorderSymbols::DOUBLE)
+            .style(Style::new().fg(Color::Red))
+            .title("top", Alignment::Center)
+            .bottom_title("bottom title", Alignment::Right);
+        backend.render_widget(&border, backend.area(), 0, 0);
+        backend.assert_text(&["╔══top══╗", "║       ║", "╚bottom…╝"]);
+        assert_eq!(backend.cell(0, 1).foreground, Color::Red.into());
+        assert_eq!(backend.cell(3, 0).foreground, Color::Red.into());
+        assert_eq!(backend.cell(4, 1).foreground, ForegroundColor::default());
     }
 }
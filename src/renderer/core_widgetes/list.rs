@@ -1,13 +1,25 @@
+use super::scrollbar::{Scrollbar, ScrollbarState};
 use crate::renderer::{
-    painter::Painter, rect::Rect, widget::Widget, BackgroundColor, Direction, ForegroundColor,
-    Simble,
+    input::{EventResult, InputEvent, InputHandler},
+    painter::Painter,
+    rect::Rect,
+    text::Style,
+    widget::{StatefulWidget, Widget},
+    BackgroundColor, Direction, Simble,
 };
+use crossterm::event::{KeyCode, MouseButton, MouseEvent, MouseEventKind};
+use std::collections::BTreeSet;
 
 pub struct ListWidget<T> {
     elements: Vec<T>,
     element_width: u16,
     element_height: u16,
     scroll: u16,
+    //used when rendered with a ListState
+    highlight_style: Style,
+    highlight_symbol: Option<Simble>,
+    marked_style: Style,
+    scrollbar: Option<Scrollbar>,
 }
 
 impl<T> ListWidget<T> {
@@ -17,8 +29,38 @@ impl<T> ListWidget<T> {
             element_width,
             element_height,
             scroll,
+            highlight_style: Style::new(),
+            highlight_symbol: None,
+            marked_style: Style::new(),
+            scrollbar: None,
         }
     }
+    //painted over the selected element
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+    //drawn before the selected element, every element is moved right by its width
+    pub fn highlight_symbol(mut self, symbol: impl Into<Simble>) -> Self {
+        self.highlight_symbol = Some(symbol.into());
+        self
+    }
+    //painted over the marked elements, under the highlight
+    pub fn marked_style(mut self, style: Style) -> Self {
+        self.marked_style = style;
+        self
+    }
+    //drawn on the last column, elements are narrowed to leave it free
+    pub fn scrollbar(mut self, scrollbar: Scrollbar) -> Self {
+        self.scrollbar = Some(scrollbar);
+        self
+    }
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
 }
 impl<P: Painter, T: Widget<P>> Widget<P> for ListWidget<T> {
     fn render_widget(&self, painter: &mut P) {
@@ -30,3 +72,408 @@ impl<P: Painter, T: Widget<P>> Widget<P> for ListWidget<T> {
         }
     }
 }
+//selected element, marked elements and first element shown of a list, kept by the
+//application between frames
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ListState {
+    offset: usize,
+    selected: Option<usize>,
+    //space toggles the mark of the selected element
+    multi_select: bool,
+    marked: BTreeSet<usize>,
+    //number of elements, elements per page and rows per element of the last render
+    len: usize,
+    page: usize,
+    step: usize,
+    //rows at the end of every step that separate elements
+    gap: usize,
+}
+impl ListState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_selected(mut self, selected: Option<usize>) -> Self {
+        self.selected = selected;
+        self
+    }
+    pub fn multi_select(mut self, multi_select: bool) -> Self {
+        self.multi_select = multi_select;
+        self
+    }
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+    //the selection is clamped to the elements when the list is rendered
+    pub fn select(&mut self, index: Option<usize>) {
+        self.selected = index;
+    }
+    pub fn select_next(&mut self) {
+        self.select_by(1);
+    }
+    pub fn select_previous(&mut self) {
+        self.select_by(-1);
+    }
+    pub fn select_first(&mut self) {
+        self.selected = Some(0);
+    }
+    //the end is only known once the list is rendered, until then this selects past it
+    pub fn select_last(&mut self) {
+        self.selected = Some(self.len.checked_sub(1).unwrap_or(usize::MAX));
+    }
+    //moves the selection, stopping at the ends of the list
+    pub fn select_by(&mut self, delta: isize) {
+        let selected = match self.selected {
+            Some(selected) => selected.saturating_add_signed(delta),
+            None => 0,
+        };
+        self.selected = Some(match self.len {
+            0 => selected,
+            len => selected.min(len - 1),
+        });
+    }
+    //moves the selection by the number of elements that fit in the list
+    pub fn page_down(&mut self) {
+        self.select_by(self.page.max(1) as isize);
+    }
+    pub fn page_up(&mut self) {
+        self.select_by(-(self.page.max(1) as isize));
+    }
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+    //moved when rendered if the selection would not be visible
+    pub fn set_offset(&mut self, offset: usize) {
+        self.offset = offset;
+    }
+    pub fn is_marked(&self, index: usize) -> bool {
+        self.marked.contains(&index)
+    }
+    //marked elements in increasing order
+    pub fn marked(&self) -> impl Iterator<Item = usize> + '_ {
+        self.marked.iter().copied()
+    }
+    pub fn toggle_mark(&mut self, index: usize) {
+        if !self.marked.remove(&index) {
+            self.marked.insert(index);
+        }
+    }
+    pub fn clear_marks(&mut self) {
+        self.marked.clear();
+    }
+    //keeps the selection and the marks inside a list of len elements
+    pub(crate) fn clamp_selection(&mut self, len: usize) {
+        self.len = len;
+        self.marked.retain(|&m| m < len);
+        if len == 0 {
+            self.selected = None;
+            self.offset = 0;
+        }
+        self.selected = self.selected.map(|s| s.min(len.saturating_sub(1)));
+    }
+    //elements shown by the last render and rows per element, gap included, step is 0 when
+    //they don't all have the same height and clicks can't be mapped to elements
+    pub(crate) fn set_page(&mut self, page: usize, step: usize, gap: usize) {
+        self.page = page;
+        self.step = step;
+        self.gap = gap;
+    }
+    //keeps the selection inside the list and visible, and the offset from leaving empty space
+    //at the end of the list
+    fn clamp(&mut self, len: usize, visible: usize) {
+        self.page = visible;
+        self.clamp_selection(len);
+        if len == 0 {
+            return;
+        }
+        self.offset = self.offset.min(len.saturating_sub(visible));
+        if let Some(selected) = self.selected {
+            if selected < self.offset {
+                self.offset = selected;
+            } else if selected >= self.offset + visible {
+                self.offset = selected + 1 - visible;
+            }
+        }
+    }
+}
+//arrows, page up and down, home and end move the selection, space marks it when multi
+//select is on, a click selects the element under it and the wheel moves the selection
+impl InputHandler for ListState {
+    fn handle_event(&mut self, event: &InputEvent) -> EventResult {
+        if let Some(delta) = event.wheel_delta() {
+            self.select_by(delta as isize);
+            return EventResult::Consumed;
+        }
+        if let InputEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::Down(MouseButton::Left),
+            row,
+            ..
+        }) = event
+        {
+            //clicks on the space between elements are ignored
+            let row = *row as usize;
+            if self.step == 0 || row % self.step >= self.step.saturating_sub(self.gap) {
+                return EventResult::Bubbled;
+            }
+            //and so are clicks under the last element
+            let index = self.offset + row / self.step;
+            if index >= self.len {
+                return EventResult::Bubbled;
+            }
+            self.selected = Some(index);
+            return EventResult::Consumed;
+        }
+        let Some(key) = event.pressed_key() else {
+            return EventResult::Bubbled;
+        };
+        match key.code {
+            KeyCode::Up => self.select_previous(),
+            KeyCode::Down => self.select_next(),
+            KeyCode::PageUp => self.page_up(),
+            KeyCode::PageDown => self.page_down(),
+            KeyCode::Home => self.select_first(),
+            KeyCode::End => self.select_last(),
+            KeyCode::Char(' ') if self.multi_select => {
+                let Some(selected) = self.selected else {
+                    return EventResult::Bubbled;
+                };
+                self.toggle_mark(selected);
+            }
+            _ => return EventResult::Bubbled,
+        }
+        EventResult::Consumed
+    }
+}
+//renders the elements starting from the offset of the state, the scrollbar takes the last
+//column of the area
+impl<P: Painter, T: Widget<P>> StatefulWidget<P> for ListWidget<T> {
+    type State = ListState;
+    fn render_stateful_widget(&self, painter: &mut P, state: &mut ListState) {
+        let area = painter.area();
+        let height = area.height;
+        let step = self.element_height as usize + 1;
+        //elements that fit entirely, there is always at least one
+        let visible = ((height as usize + 1) / step).max(1);
+        state.clamp(self.elements.len(), visible);
+        state.step = step;
+        state.gap = 1;
+        let symbol_width = self.highlight_symbol.as_ref().map_or(0, |s| s.width());
+        let element_width = match self.scrollbar {
+            Some(_) => self
+                .element_width
+                .min(area.width.saturating_sub(symbol_width + 1)),
+            None => self.element_width,
+        };
+        let mut y = 0u16;
+        for (i, e) in self.elements.iter().enumerate().skip(state.offset) {
+            if y >= height {
+                break;
+            }
+            let area = Rect::new(symbol_width, y, element_width, self.element_height);
+            painter.render_widget(e, area, 0, self.scroll);
+            let selected = state.selected == Some(i);
+            let row = Rect::new(0, y, symbol_width + element_width, self.element_height);
+            if state.is_marked(i) {
+                self.marked_style.fill(painter, Some(row));
+            }
+            if selected {
+                self.highlight_style.fill(painter, Some(row));
+            }
+            if let Some(symbol) = self.highlight_symbol.as_ref().filter(|_| selected) {
+                let simbles = Simble::from_text(symbol.as_str());
+                painter.write_simbles(&simbles, Rect::new(0, y, symbol_width, 1));
+            }
+            y = y.saturating_add(step as u16);
+        }
+        if let Some(scrollbar) = &self.scrollbar {
+            let content = (self.elements.len() * step).saturating_sub(1);
+            let mut scrollbar_state =
+                ScrollbarState::new(content.min(u16::MAX as usize) as u16, height)
+                    .position((state.offset * step).min(u16::MAX as usize) as u16);
+            let bar = Rect::new(area.width.saturating_sub(1), 0, 1, height);
+            painter.render_stateful_widget(scrollbar, &mut scrollbar_state, bar, 0, 0);
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{
+        painter::TextPainer,
+        test_backend::{left_click, TB},
+    };
+    use crossterm::{
+        event::{KeyEvent, KeyModifiers},
+        style::Color,
+    };
+
+    #[derive(Clone)]
+    struct Count;
+    impl<P: Painter + TextPainer> Widget<P> for Count {
+        fn render_widget(&self, painter: &mut P) {
+            for i in 0..5 {
+                painter.write_text_line(i.to_string().as_str(), 0, i);
+            }
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let mut backend = TB::new(2, 7);
+        let list = ListWidget::new(vec![Count, Count], 0, 1, 3);
+        backend.render_widget(&list, backend.area(), 0, 0);
+        backend.assert_text(&["0 ", "1 ", "2 ", "  ", "0 ", "1 ", "2 "]);
+    }
+
+    #[test]
+    fn test_render_scrolled_elements() {
+        let mut backend = TB::new(1, 7);
+        let list = ListWidget::new(vec![Count, Count], 2, 1, 3);
+        backend.render_widget(&list, backend.area(), 0, 0);
+        backend.assert_text(&["2", "3", "4", " ", "2", "3", "4"]);
+    }
+
+    #[test]
+    fn test_render_scrolled_list() {
+        let mut backend = TB::new(1, 4);
+        let list = ListWidget::new(vec![Count, Count], 0, 1, 3);
+        backend.render_widget(&list, backend.area(), 0, 2);
+        backend.assert_text(&["2", " ", "0", "1"]);
+    }
+
+    #[test]
+    fn test_render_stateful_follows_selection() {
+        let mut backend = TB::new(1, 7);
+        let list = ListWidget::new(vec![Count; 5], 0, 1, 3);
+        let mut state = ListState::new().with_selected(Some(3));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.offset(), 2);
+        backend.assert_text(&["0", "1", "2", " ", "0", "1", "2"]);
+        state.select(Some(9));
+        state.set_offset(0);
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.selected(), Some(4));
+        assert_eq!(state.offset(), 3);
+        state.select(Some(0));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.offset(), 0);
+    }
+
+    #[test]
+    fn test_render_stateful_empty() {
+        let mut backend = TB::new(1, 3);
+        let list: ListWidget<Count> = ListWidget::new(Vec::new(), 0, 1, 3);
+        let mut state = ListState::new().with_selected(Some(2));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.selected(), None);
+    }
+
+    fn press(state: &mut ListState, code: KeyCode) -> EventResult {
+        state.handle_event(&KeyEvent::new(code, KeyModifiers::NONE).into())
+    }
+
+    #[test]
+    fn test_highlight() {
+        let mut backend = TB::new(3, 7);
+        let list = ListWidget::new(vec![Count; 3], 0, 1, 3)
+            .highlight_symbol('>')
+            .highlight_style(Style::new().bg(Color::Blue));
+        let mut state = ListState::new().with_selected(Some(1));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        backend.assert_text(&[" 0 ", " 1 ", " 2 ", "   ", ">0 ", " 1 ", " 2 "]);
+        assert_eq!(backend.cell(1, 4).background, Color::Blue.into());
+        assert_eq!(backend.cell(1, 0).background, BackgroundColor::default());
+    }
+
+    #[test]
+    fn test_key_navigation() {
+        let mut backend = TB::new(1, 7);
+        let list = ListWidget::new(vec![Count; 10], 0, 1, 3);
+        let mut state = ListState::new();
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(press(&mut state, KeyCode::Down), EventResult::Consumed);
+        assert_eq!(state.selected(), Some(0));
+        press(&mut state, KeyCode::PageDown);
+        assert_eq!(state.selected(), Some(2));
+        press(&mut state, KeyCode::End);
+        assert_eq!(state.selected(), Some(9));
+        press(&mut state, KeyCode::Down);
+        assert_eq!(state.selected(), Some(9));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.offset(), 8);
+        press(&mut state, KeyCode::Home);
+        assert_eq!(state.selected(), Some(0));
+        assert_eq!(press(&mut state, KeyCode::Enter), EventResult::Bubbled);
+    }
+
+    #[test]
+    fn test_multi_select() {
+        let mut state = ListState::new().multi_select(true);
+        assert_eq!(press(&mut state, KeyCode::Char(' ')), EventResult::Bubbled);
+        state.select(Some(2));
+        press(&mut state, KeyCode::Char(' '));
+        state.select(Some(0));
+        press(&mut state, KeyCode::Char(' '));
+        assert_eq!(state.marked().collect::<Vec<_>>(), vec![0, 2]);
+        press(&mut state, KeyCode::Char(' '));
+        assert!(!state.is_marked(0));
+        //marks of elements that no longer exist are dropped when rendered
+        let mut backend = TB::new(1, 3);
+        let list = ListWidget::new(vec![Count; 2], 0, 1, 1);
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.marked().count(), 0);
+    }
+
+    #[test]
+    fn test_click_selects() {
+        let mut backend = TB::new(1, 7);
+        let list = ListWidget::new(vec![Count; 3], 0, 1, 3);
+        let mut state = ListState::new();
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.handle_event(&left_click(0, 3)), EventResult::Bubbled);
+        state.handle_event(&left_click(0, 5));
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_click_under_short_list() {
+        let mut backend = TB::new(1, 7);
+        let list = ListWidget::new(vec![Count; 2], 0, 1, 1);
+        let mut state = ListState::new().with_selected(Some(1));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.handle_event(&left_click(0, 4)), EventResult::Bubbled);
+        assert_eq!(state.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_attached_scrollbar() {
+        let mut backend = TB::new(2, 7);
+        let scrollbar = Scrollbar::new(
+            2,
+            0,
+            1,
+            '|'.into(),
+            '#'.into(),
+            '^'.into(),
+            'v'.into(),
+            Direction::Down,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .unwrap();
+        let list = ListWidget::new(vec![Count; 6], 0, 1, 3).scrollbar(scrollbar);
+        let mut state = ListState::new().with_selected(Some(5));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        backend.assert_text(&["0^", "1|", "2|", " |", "0|", "1#", "2v"]);
+        //elements wider than the area leave the scrollbar column alone
+        let list = ListWidget::new(vec![Count; 6], 0, 2, 3)
+            .highlight_style(Style::new().bg(Color::Blue))
+            .scrollbar(list.scrollbar.unwrap());
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(backend.cell(0, 5).background, Color::Blue.into());
+        assert_eq!(backend.cell(1, 5).background, BackgroundColor::default());
+    }
+}
@@ -1,3 +1,7 @@
+pub mod block;
 pub mod border;
 pub mod list;
 pub mod scrollbar;
+pub mod table;
+pub mod tree;
+pub mod virtual_list;
@@ -1,6 +1,11 @@
-use crate::renderer::{
-    painter::Painter, rect::Rect, widget::Widget, BackgroundColor, Direction, ForegroundColor,
-    Simble,
+use crate::{
+    error::{Error, Result},
+    renderer::{
+        painter::Painter,
+        rect::Rect,
+        widget::{StatefulWidget, Widget},
+        BackgroundColor, Direction, ForegroundColor, Simble,
+    },
 };
 
 pub struct Scrollbar {
@@ -36,14 +41,18 @@ impl Scrollbar {
         foreground_color_grabbable: Option<ForegroundColor>,
         background_color_arrow: Option<BackgroundColor>,
         foreground_color_arrow: Option<ForegroundColor>,
-    ) -> Self {
+    ) -> Result<Self> {
         if lenght < 2 {
-            panic!("scrollbars require at least a lenght of 2")
+            return Err(Error::ScrollbarTooShort { lenght });
         }
-        if grabbable_lenght + position > lenght {
-            panic!("grabbable element should fit inside the lenght of the bar")
+        if grabbable_lenght as u32 + position as u32 > lenght as u32 {
+            return Err(Error::GrabbableOutOfBounds {
+                lenght,
+                grabbable_lenght,
+                position,
+            });
         }
-        Self {
+        Ok(Self {
             lenght,
             grabbable_lenght,
             position,
@@ -58,61 +67,70 @@ impl Scrollbar {
             foreground_color_grabbable,
             background_color_arrow,
             foreground_color_arrow,
-        }
+        })
     }
-    fn handle_direction(&self, start: u16, lenght: u16) -> (Rect, bool) {
+    //area of a part of a bar of the given lenght and if its content has to be reversed
+    fn handle_direction(&self, bar_lenght: u16, start: u16, lenght: u16) -> (Rect, bool) {
         match self.direction {
             Direction::UP => (
-                Rect::new(0, self.lenght - (start + lenght - 1), 1, lenght),
+                Rect::new(
+                    0,
+                    bar_lenght.saturating_sub(start.saturating_add(lenght)),
+                    1,
+                    lenght,
+                ),
                 true,
             ),
             Direction::Down => (Rect::new(0, start, 1, lenght), false),
             Direction::Left => (
-                Rect::new(self.lenght - (start + lenght - 1), 0, lenght, 1),
+                Rect::new(
+                    bar_lenght.saturating_sub(start.saturating_add(lenght)),
+                    0,
+                    lenght,
+                    1,
+                ),
                 true,
             ),
             Direction::Right => (Rect::new(start, 0, lenght, 1), false),
         }
     }
-}
-impl<P: Painter> Widget<P> for Scrollbar {
-    fn render_widget<'b>(&self, painter: &'b mut P) {
-        let mut simbles = vec![self.bar_simble.clone(); self.lenght as usize];
-        let grabbable = vec![self.grabbable_simble.clone(); self.grabbable_lenght as usize];
+    fn draw<P: Painter>(&self, painter: &mut P, lenght: u16, grabbable_lenght: u16, position: u16) {
+        let mut simbles = vec![self.bar_simble.clone(); lenght as usize];
+        let grabbable = vec![self.grabbable_simble.clone(); grabbable_lenght as usize];
         let scrollbar_slice =
-            &mut simbles[self.position as usize..(self.position + self.grabbable_lenght) as usize];
+            &mut simbles[position as usize..(position + grabbable_lenght) as usize];
         let grabbable_slice = grabbable.as_slice();
         scrollbar_slice.clone_from_slice(grabbable_slice);
         simbles[0] = self.less_arrow_simble.clone();
-        simbles[(self.lenght - 1) as usize] = self.more_arrow_simble.clone();
+        simbles[(lenght - 1) as usize] = self.more_arrow_simble.clone();
 
-        let s = self.handle_direction(0, self.lenght);
+        let s = self.handle_direction(lenght, 0, lenght);
         if s.1 {
             simbles.reverse();
         }
         painter.write_simbles(&simbles, s.0);
 
         if let Some(color) = self.background_color {
-            let t = vec![color; self.lenght as usize];
-            let bc = self.handle_direction(0, self.lenght);
+            let t = vec![color; lenght as usize];
+            let bc = self.handle_direction(lenght, 0, lenght);
             painter.write_background_color(&t, bc.0);
         }
         if let Some(color) = self.foreground_color {
-            let t = vec![color; self.lenght as usize];
-            let fc = self.handle_direction(0, self.lenght);
+            let t = vec![color; lenght as usize];
+            let fc = self.handle_direction(lenght, 0, lenght);
             painter.write_foreground_color(&t, fc.0);
         }
         if let Some(color) = self.background_color_grabbable {
-            let mut t = vec![color; self.grabbable_lenght as usize];
-            let bc = self.handle_direction(self.position, self.grabbable_lenght);
+            let mut t = vec![color; grabbable_lenght as usize];
+            let bc = self.handle_direction(lenght, position, grabbable_lenght);
             if bc.1 {
                 t.reverse();
             }
             painter.write_background_color(&t, bc.0);
         }
         if let Some(color) = self.foreground_color_grabbable {
-            let mut t = vec![color; self.grabbable_lenght as usize];
-            let fc = self.handle_direction(self.position, self.grabbable_lenght);
+            let mut t = vec![color; grabbable_lenght as usize];
+            let fc = self.handle_direction(lenght, position, grabbable_lenght);
             if fc.1 {
                 t.reverse();
             }
@@ -120,17 +138,267 @@ impl<P: Painter> Widget<P> for Scrollbar {
         }
         if let Some(color) = self.background_color_arrow {
             let t = vec![color; 1];
-            let bc = self.handle_direction(0, 1);
+            let bc = self.handle_direction(lenght, 0, 1);
             painter.write_background_color(&t, bc.0);
-            let bc = self.handle_direction(self.lenght - 1, 1);
+            let bc = self.handle_direction(lenght, lenght - 1, 1);
             painter.write_background_color(&t, bc.0);
         }
         if let Some(color) = self.foreground_color_arrow {
             let t = vec![color; 1];
-            let fc = self.handle_direction(0, 1);
+            let fc = self.handle_direction(lenght, 0, 1);
             painter.write_foreground_color(&t, fc.0);
-            let fc = self.handle_direction(self.lenght - 1, 1);
+            let fc = self.handle_direction(lenght, lenght - 1, 1);
             painter.write_foreground_color(&t, fc.0);
         }
     }
 }
+impl<P: Painter> Widget<P> for Scrollbar {
+    fn render_widget<'b>(&self, painter: &'b mut P) {
+        self.draw(painter, self.lenght, self.grabbable_lenght, self.position);
+    }
+}
+//how much content a scrollbar scrolls through, kept by the application between frames
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ScrollbarState {
+    //lenght of the whole content and of the part of it that is visible
+    pub content_lenght: u16,
+    pub viewport_lenght: u16,
+    //first visible position of the content, clamped when rendered
+    pub position: u16,
+}
+impl ScrollbarState {
+    pub fn new(content_lenght: u16, viewport_lenght: u16) -> Self {
+        Self {
+            content_lenght,
+            viewport_lenght,
+            position: 0,
+        }
+    }
+    pub fn position(mut self, position: u16) -> Self {
+        self.position = position;
+        self
+    }
+    pub fn max_position(&self) -> u16 {
+        self.content_lenght.saturating_sub(self.viewport_lenght)
+    }
+    pub fn scroll_by(&mut self, delta: i32) {
+        let position = (self.position as i32 + delta).clamp(0, self.max_position() as i32);
+        self.position = position as u16;
+    }
+    //grabbable lenght and position inside a bar of the given lenght, arrows included
+    fn grabbable(&self, lenght: u16) -> (u16, u16) {
+        let track = lenght.saturating_sub(2) as u32;
+        if track == 0 {
+            return (0, 1);
+        }
+        let content = self.content_lenght.max(1) as u32;
+        let grabbable = (track * self.viewport_lenght as u32 / content).clamp(1, track);
+        let max_position = self.max_position() as u32;
+        let offset = ((track - grabbable) * self.position as u32)
+            .checked_div(max_position)
+            .unwrap_or(0);
+        (grabbable as u16, 1 + offset as u16)
+    }
+}
+//the bar takes the lenght of the area it is rendered in and the grabbable part is sized
+//from the state, the lenght, grabbable lenght and position given to new are not used
+impl<P: Painter> StatefulWidget<P> for Scrollbar {
+    type State = ScrollbarState;
+    fn render_stateful_widget(&self, painter: &mut P, state: &mut ScrollbarState) {
+        state.position = state.position.min(state.max_position());
+        let area = painter.area();
+        let lenght = match self.direction {
+            Direction::UP | Direction::Down => area.height,
+            Direction::Left | Direction::Right => area.width,
+        };
+        if lenght < 2 {
+            return;
+        }
+        let (grabbable_lenght, position) = state.grabbable(lenght);
+        self.draw(painter, lenght, grabbable_lenght, position);
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::test_backend::TB;
+    use crossterm::style::Color;
+
+    fn scrollbar(direction: Direction) -> Scrollbar {
+        Scrollbar::new(
+            6,
+            2,
+            2,
+            '-'.into(),
+            '#'.into(),
+            '<'.into(),
+            '>'.into(),
+            direction,
+            None,
+            None,
+            Some(Color::Red.into()),
+            None,
+            None,
+            None,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_right() {
+        let mut backend = TB::new(6, 1);
+        backend.render_widget(&scrollbar(Direction::Right), backend.area(), 0, 0);
+        backend.assert_text(&["<-##->"]);
+        assert_eq!(backend.cell(2, 0).background, Color::Red.into());
+        assert_eq!(backend.cell(3, 0).background, Color::Red.into());
+        assert_eq!(backend.cell(1, 0).background, BackgroundColor::default());
+    }
+
+    #[test]
+    fn test_render_down() {
+        let mut backend = TB::new(1, 6);
+        backend.render_widget(&scrollbar(Direction::Down), backend.area(), 0, 0);
+        backend.assert_text(&["<", "-", "#", "#", "-", ">"]);
+    }
+
+    #[test]
+    fn test_render_up() {
+        let mut backend = TB::new(1, 6);
+        backend.render_widget(&scrollbar(Direction::UP), backend.area(), 0, 0);
+        backend.assert_text(&[">", "-", "#", "#", "-", "<"]);
+        assert_eq!(backend.cell(0, 2).background, Color::Red.into());
+        assert_eq!(backend.cell(0, 3).background, Color::Red.into());
+        assert_eq!(backend.cell(0, 4).background, BackgroundColor::default());
+    }
+
+    #[test]
+    fn test_render_left() {
+        let mut backend = TB::new(6, 1);
+        backend.render_widget(&scrollbar(Direction::Left), backend.area(), 0, 0);
+        backend.assert_text(&[">-##-<"]);
+    }
+
+    #[test]
+    fn test_render_stateful_up() {
+        let mut backend = TB::new(1, 10);
+        let mut state = ScrollbarState::new(40, 10).position(30);
+        backend.render_stateful_widget(&scrollbar(Direction::UP), &mut state, backend.area(), 0, 0);
+        backend.assert_text(&[">", "#", "#", "-", "-", "-", "-", "-", "-", "<"]);
+    }
+
+    #[test]
+    fn test_render_empty_grabbable_reversed() {
+        for direction in [Direction::UP, Direction::Left] {
+            let scrollbar = Scrollbar::new(
+                4,
+                0,
+                0,
+                '-'.into(),
+                '#'.into(),
+                '<'.into(),
+                '>'.into(),
+                direction,
+                None,
+                None,
+                Some(Color::Red.into()),
+                None,
+                None,
+                None,
+            )
+            .unwrap();
+            let mut backend = TB::new(4, 4);
+            backend.render_widget(&scrollbar, backend.area(), 0, 0);
+            for i in 0..4 {
+                assert_ne!(backend.cell(0, i).background, Color::Red.into());
+                assert_ne!(backend.cell(i, 0).background, Color::Red.into());
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_stateful() {
+        let mut backend = TB::new(1, 10);
+        let mut state = ScrollbarState::new(40, 10).position(30);
+        backend.render_stateful_widget(
+            &scrollbar(Direction::Down),
+            &mut state,
+            backend.area(),
+            0,
+            0,
+        );
+        backend.assert_text(&["<", "-", "-", "-", "-", "-", "-", "#", "#", ">"]);
+        //the position is clamped to the end of the content
+        state.position = 100;
+        backend.render_stateful_widget(
+            &scrollbar(Direction::Right),
+            &mut state,
+            Rect::new(0, 0, 1, 10),
+            0,
+            0,
+        );
+        assert_eq!(state.position, 30);
+    }
+
+    #[test]
+    fn test_scroll_by() {
+        let mut state = ScrollbarState::new(20, 5);
+        state.scroll_by(-3);
+        assert_eq!(state.position, 0);
+        state.scroll_by(40);
+        assert_eq!(state.position, 15);
+        assert_eq!(ScrollbarState::new(20, 5).grabbable(12), (2, 1));
+        assert_eq!(ScrollbarState::new(3, 5).grabbable(12), (10, 1));
+    }
+
+    #[test]
+    fn test_grabbable_too_long() {
+        let scrollbar = Scrollbar::new(
+            3,
+            3,
+            1,
+            '-'.into(),
+            '#'.into(),
+            '<'.into(),
+            '>'.into(),
+            Direction::Down,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert!(matches!(
+            scrollbar,
+            Err(Error::GrabbableOutOfBounds {
+                lenght: 3,
+                grabbable_lenght: 3,
+                position: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_too_short() {
+        let scrollbar = Scrollbar::new(
+            1,
+            0,
+            0,
+            '-'.into(),
+            '#'.into(),
+            '<'.into(),
+            '>'.into(),
+            Direction::Down,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert!(matches!(
+            scrollbar,
+            Err(Error::ScrollbarTooShort { lenght: 1 })
+        ));
+    }
+}
@@ -0,0 +1,489 @@
+use super::{list::ListState, virtual_list::visible_offset};
+use crate::renderer::{
+    input::{EventResult, InputEvent, InputHandler},
+    layout::{Constraint, Layout},
+    painter::{
+        paragraph::{Alignment, ParagraphLayout, Wrap},
+        Painter, TextPainer,
+    },
+    rect::Rect,
+    text::{Style, Text},
+    widget::{StatefulWidget, Widget},
+    Simble,
+};
+use crossterm::event::{KeyCode, MouseEvent};
+
+//content of a cell, cut with an ellipsis when wider than its column
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cell {
+    pub content: Text,
+    //None uses the alignment of the column
+    pub alignment: Option<Alignment>,
+    pub style: Style,
+}
+impl Cell {
+    pub fn new(content: impl Into<Text>) -> Self {
+        Self {
+            content: content.into(),
+            alignment: None,
+            style: Style::new(),
+        }
+    }
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = Some(alignment);
+        self
+    }
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+impl<T: Into<Text>> From<T> for Cell {
+    fn from(content: T) -> Self {
+        Self::new(content)
+    }
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Row {
+    pub cells: Vec<Cell>,
+    pub height: u16,
+    pub style: Style,
+}
+impl Row {
+    pub fn new<C: Into<Cell>>(cells: impl IntoIterator<Item = C>) -> Self {
+        Self {
+            cells: cells.into_iter().map(Into::into).collect(),
+            height: 1,
+            style: Style::new(),
+        }
+    }
+    pub fn height(mut self, height: u16) -> Self {
+        self.height = height.max(1);
+        self
+    }
+    pub fn style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+impl SortOrder {
+    pub fn toggle(self) -> Self {
+        match self {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        }
+    }
+    fn indicator(self) -> char {
+        match self {
+            SortOrder::Ascending => '▲',
+            SortOrder::Descending => '▼',
+        }
+    }
+}
+//selected row, first row shown and horizontal scroll of a table
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableState {
+    list: ListState,
+    scroll_x: u16,
+    //columns hidden on the right in the last render
+    max_scroll_x: u16,
+    //rows taken by the header and its separator in the last render
+    header_height: u16,
+}
+impl TableState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_selected(mut self, selected: Option<usize>) -> Self {
+        self.list.select(selected);
+        self
+    }
+    pub fn selected(&self) -> Option<usize> {
+        self.list.selected()
+    }
+    pub fn select(&mut self, index: Option<usize>) {
+        self.list.select(index);
+    }
+    pub fn offset(&self) -> usize {
+        self.list.offset()
+    }
+    //selection and vertical scroll, the rows work like the elements of a list
+    pub fn list(&self) -> &ListState {
+        &self.list
+    }
+    pub fn list_mut(&mut self) -> &mut ListState {
+        &mut self.list
+    }
+    pub fn scroll_x(&self) -> u16 {
+        self.scroll_x
+    }
+    //clamped when the table is rendered
+    pub fn set_scroll_x(&mut self, scroll_x: u16) {
+        self.scroll_x = scroll_x;
+    }
+    pub fn scroll_left(&mut self) {
+        self.scroll_x = self.scroll_x.saturating_sub(1);
+    }
+    pub fn scroll_right(&mut self) {
+        self.scroll_x = self.scroll_x.saturating_add(1).min(self.max_scroll_x);
+    }
+}
+//keys of a list for the rows, left and right scroll the columns, clicks on the header bubble
+impl InputHandler for TableState {
+    fn handle_event(&mut self, event: &InputEvent) -> EventResult {
+        //the wheel scrolls the rows wherever it is
+        if let (InputEvent::Mouse(mouse), None) = (event, event.wheel_delta()) {
+            let Some(row) = mouse.row.checked_sub(self.header_height) else {
+                return EventResult::Bubbled;
+            };
+            return self
+                .list
+                .handle_event(&InputEvent::Mouse(MouseEvent { row, ..*mouse }));
+        }
+        match event.pressed_key().map(|key| key.code) {
+            Some(KeyCode::Left) => self.scroll_left(),
+            Some(KeyCode::Right) => self.scroll_right(),
+            _ => return self.list.handle_event(event),
+        }
+        EventResult::Consumed
+    }
+}
+//rows and columns with a header that stays on top while the rows scroll, columns wider
+//than the area are scrolled horizontally
+pub struct Table {
+    header: Option<Row>,
+    rows: Vec<Row>,
+    widths: Vec<Constraint>,
+    alignments: Vec<Alignment>,
+    //columns are laid out on at least this width, more than the area scrolls horizontally
+    min_width: u16,
+    column_spacing: u16,
+    column_separator: Option<Simble>,
+    header_separator: Option<Simble>,
+    //drawn where the two separators cross
+    crossing: Simble,
+    header_style: Style,
+    highlight_style: Style,
+    sort: Option<(usize, SortOrder)>,
+}
+impl Table {
+    pub fn new(rows: impl Into<Vec<Row>>, widths: impl Into<Vec<Constraint>>) -> Self {
+        Self {
+            header: None,
+            rows: rows.into(),
+            widths: widths.into(),
+            alignments: Vec::new(),
+            min_width: 0,
+            column_spacing: 1,
+            column_separator: Some('│'.into()),
+            header_separator: Some('─'.into()),
+            crossing: '┼'.into(),
+            header_style: Style::new(),
+            highlight_style: Style::new(),
+            sort: None,
+        }
+    }
+    pub fn header(mut self, header: Row) -> Self {
+        self.header = Some(header);
+        self
+    }
+    //alignment of the cells of every column, missing columns are aligned left
+    pub fn alignments(mut self, alignments: impl Into<Vec<Alignment>>) -> Self {
+        self.alignments = alignments.into();
+        self
+    }
+    pub fn min_width(mut self, width: u16) -> Self {
+        self.min_width = width;
+        self
+    }
+    //space between columns, the column separator is drawn on its first column
+    pub fn column_spacing(mut self, spacing: u16) -> Self {
+        self.column_spacing = spacing;
+        self
+    }
+    pub fn column_separator(mut self, separator: impl Into<Simble>) -> Self {
+        self.column_separator = Some(separator.into());
+        self
+    }
+    //drawn on the line under the header
+    pub fn header_separator(mut self, separator: impl Into<Simble>) -> Self {
+        self.header_separator = Some(separator.into());
+        self
+    }
+    pub fn crossing(mut self, crossing: impl Into<Simble>) -> Self {
+        self.crossing = crossing.into();
+        self
+    }
+    pub fn without_separators(mut self) -> Self {
+        self.column_separator = None;
+        self.header_separator = None;
+        self
+    }
+    pub fn header_style(mut self, style: Style) -> Self {
+        self.header_style = style;
+        self
+    }
+    //painted over the selected row
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+    //shows an arrow at the end of the header of the column
+    pub fn sort(mut self, column: usize, order: SortOrder) -> Self {
+        self.sort = Some((column, order));
+        self
+    }
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+    //rows taken by the header and the line under it
+    fn header_height(&self) -> u16 {
+        match &self.header {
+            Some(header) => header.height + self.header_separator.is_some() as u16,
+            None => 0,
+        }
+    }
+    fn columns(&self, width: u16) -> Vec<Rect> {
+        Layout::horizontal(self.widths.clone())
+            .spacing(self.column_spacing)
+            .split(Rect::new(0, 0, width, 1))
+    }
+    //writes the cells of a row and the separators between them
+    fn draw_row<P: Painter>(&self, painter: &mut P, row: &Row, columns: &[Rect], y: u16) {
+        let width = columns.last().map_or(0, |c| c.right());
+        let line = Rect::new(0, y, width, row.height);
+        row.style.fill(painter, Some(line));
+        for (i, (column, cell)) in columns.iter().zip(&row.cells).enumerate() {
+            let alignment = cell
+                .alignment
+                .or(self.alignments.get(i).copied())
+                .unwrap_or_default();
+            let layout = ParagraphLayout::new()
+                .wrap(Wrap::None)
+                .alignment(alignment)
+                .ellipsis('…');
+            let area = Rect::new(column.x, y, column.width, row.height);
+            cell.style.fill(painter, Some(area));
+            painter.write_text(&cell.content, area, &layout);
+        }
+        if let Some(separator) = &self.column_separator {
+            let simbles = vec![separator.clone(); row.height as usize];
+            for column in columns.iter().take(columns.len().saturating_sub(1)) {
+                if self.column_spacing > 0 {
+                    painter.write_simbles(&simbles, Rect::new(column.right(), y, 1, row.height));
+                }
+            }
+        }
+    }
+    fn draw_header<P: Painter>(&self, painter: &mut P, columns: &[Rect]) {
+        let Some(header) = &self.header else {
+            return;
+        };
+        //the arrow takes the last column of the cell so it is never cut
+        let mut cut = columns.to_vec();
+        let indicator = self.sort.and_then(|(column, order)| {
+            let area = cut.get_mut(column).filter(|area| area.width >= 2)?;
+            area.width -= 2;
+            Some((area.right() + 1, order.indicator()))
+        });
+        self.draw_row(painter, header, &cut, 0);
+        if let Some((x, arrow)) = indicator {
+            let simbles = vec![arrow.into(); header.height as usize];
+            painter.write_simbles(&simbles, Rect::new(x, 0, 1, header.height));
+        }
+        let width = columns.last().map_or(0, |c| c.right());
+        self.header_style
+            .fill(painter, Some(Rect::new(0, 0, width, header.height)));
+        if let Some(separator) = &self.header_separator {
+            let mut line = vec![separator.clone(); width as usize];
+            if self.column_separator.is_some() && self.column_spacing > 0 {
+                for column in columns.iter().take(columns.len().saturating_sub(1)) {
+                    if let Some(simble) = line.get_mut(column.right() as usize) {
+                        *simble = self.crossing.clone();
+                    }
+                }
+            }
+            painter.write_simbles(&line, Rect::new(0, header.height, width, 1));
+        }
+    }
+}
+//parts of the table rendered as inner widgets so the painter scrolls them horizontally
+struct HeaderView<'a> {
+    table: &'a Table,
+    columns: &'a [Rect],
+}
+impl<P: Painter> Widget<P> for HeaderView<'_> {
+    fn render_widget(&self, painter: &mut P) {
+        self.table.draw_header(painter, self.columns);
+    }
+}
+struct BodyView<'a> {
+    table: &'a Table,
+    columns: &'a [Rect],
+    offset: usize,
+    selected: Option<usize>,
+}
+impl<P: Painter> Widget<P> for BodyView<'_> {
+    fn render_widget(&self, painter: &mut P) {
+        let height = painter.area().height;
+        let width = self.columns.last().map_or(0, |c| c.right());
+        let mut y = 0u16;
+        for (i, row) in self.table.rows.iter().enumerate().skip(self.offset) {
+            if y >= height {
+                break;
+            }
+            self.table.draw_row(painter, row, self.columns, y);
+            if self.selected == Some(i) {
+                let line = Rect::new(0, y, width, row.height);
+                self.table.highlight_style.fill(painter, Some(line));
+            }
+            y = y.saturating_add(row.height);
+        }
+    }
+}
+impl<P: Painter> StatefulWidget<P> for Table {
+    type State = TableState;
+    fn render_stateful_widget(&self, painter: &mut P, state: &mut TableState) {
+        let area = painter.area();
+        let width = area.width.max(self.min_width);
+        state.max_scroll_x = width - area.width;
+        state.scroll_x = state.scroll_x.min(state.max_scroll_x);
+        let columns = self.columns(width);
+        let header_height = self.header_height().min(area.height);
+        state.header_height = header_height;
+        if header_height > 0 {
+            let header = HeaderView {
+                table: self,
+                columns: &columns,
+            };
+            let header_area = Rect::new(0, 0, area.width, header_height);
+            painter.render_widget(&header, header_area, state.scroll_x, 0);
+        }
+        let body_height = area.height - header_height;
+        state.list.clamp_selection(self.rows.len());
+        let offset = visible_offset(&state.list, self.rows.len(), body_height, |i| {
+            self.rows[i].height
+        });
+        state.list.set_offset(offset);
+        let uniform = self.rows.iter().all(|row| row.height == 1);
+        let shown = self
+            .rows
+            .iter()
+            .skip(offset)
+            .scan(0u16, |y, row| {
+                let shown = *y < body_height;
+                *y = y.saturating_add(row.height);
+                shown.then_some(())
+            })
+            .count();
+        state.list.set_page(shown, if uniform { 1 } else { 0 }, 0);
+        let body = BodyView {
+            table: self,
+            columns: &columns,
+            offset,
+            selected: state.list.selected(),
+        };
+        let body_area = Rect::new(0, header_height, area.width, body_height);
+        painter.render_widget(&body, body_area, state.scroll_x, 0);
+    }
+}
+//renders the first rows, scrolled to the left
+impl<P: Painter> Widget<P> for Table {
+    fn render_widget(&self, painter: &mut P) {
+        self.render_stateful_widget(painter, &mut TableState::default());
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{
+        test_backend::{left_click, TB},
+        BackgroundColor,
+    };
+    use crossterm::{
+        event::{KeyEvent, KeyModifiers, MouseEventKind},
+        style::Color,
+    };
+    use Constraint::*;
+
+    fn table() -> Table {
+        let rows: Vec<Row> = (1..=5)
+            .map(|i| Row::new([format!("job{}", i), (i * 10).to_string()]))
+            .collect();
+        Table::new(rows, [Length(5), Length(4)])
+            .header(Row::new(["name", "cpu"]))
+            .alignments([Alignment::Left, Alignment::Right])
+    }
+
+    #[test]
+    fn test_render() {
+        let mut backend = TB::new(10, 4);
+        backend.render_widget(&table(), backend.area(), 0, 0);
+        backend.assert_text(&["name │ cpu", "─────┼────", "job1 │  10", "job2 │  20"]);
+    }
+
+    #[test]
+    fn test_truncation_and_sort_indicator() {
+        let mut backend = TB::new(10, 3);
+        let table = Table::new([Row::new(["a long name", "1"])], [Length(5), Length(4)])
+            .header(Row::new(["name", "value"]))
+            .sort(1, SortOrder::Descending)
+            .without_separators()
+            .column_spacing(1);
+        backend.render_widget(&table, backend.area(), 0, 0);
+        backend.assert_text(&["name  v… ▼", "a lo… 1   ", "          "]);
+    }
+
+    #[test]
+    fn test_sticky_header_and_selection() {
+        let mut backend = TB::new(10, 4);
+        let table = table().highlight_style(Style::new().bg(Color::Blue));
+        let mut state = TableState::new().with_selected(Some(4));
+        backend.render_stateful_widget(&table, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.offset(), 3);
+        backend.assert_text(&["name │ cpu", "─────┼────", "job4 │  40", "job5 │  50"]);
+        assert_eq!(backend.cell(0, 3).background, Color::Blue.into());
+        assert_eq!(backend.cell(0, 2).background, BackgroundColor::default());
+    }
+
+    #[test]
+    fn test_horizontal_scroll() {
+        let mut backend = TB::new(6, 3);
+        let table = table().min_width(10);
+        let mut state = TableState::new();
+        let right = KeyEvent::new(KeyCode::Right, KeyModifiers::NONE).into();
+        backend.render_stateful_widget(&table, &mut state, backend.area(), 0, 0);
+        for _ in 0..10 {
+            state.handle_event(&right);
+        }
+        assert_eq!(state.scroll_x(), 4);
+        backend.reset();
+        backend.render_stateful_widget(&table, &mut state, backend.area(), 0, 0);
+        backend.assert_text(&[" │ cpu", "─┼────", " │  10"]);
+    }
+
+    #[test]
+    fn test_click_selects_row() {
+        let mut backend = TB::new(10, 5);
+        let mut state = TableState::new();
+        state.list_mut().set_offset(1);
+        backend.render_stateful_widget(&table(), &mut state, backend.area(), 0, 0);
+        assert_eq!(state.handle_event(&left_click(0, 1)), EventResult::Bubbled);
+        assert_eq!(state.selected(), None);
+        assert_eq!(state.handle_event(&left_click(0, 3)), EventResult::Consumed);
+        assert_eq!(state.selected(), Some(2));
+        let wheel = InputEvent::Mouse(MouseEvent {
+            kind: MouseEventKind::ScrollDown,
+            column: 0,
+            row: 0,
+            modifiers: KeyModifiers::NONE,
+        });
+        assert_eq!(state.handle_event(&wheel), EventResult::Consumed);
+        assert_eq!(state.selected(), Some(3));
+    }
+}
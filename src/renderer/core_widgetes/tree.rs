@@ -0,0 +1,396 @@
+use super::{list::ListState, virtual_list::visible_offset};
+use crate::renderer::{
+    input::{EventResult, InputEvent, InputHandler},
+    painter::{
+        paragraph::{ParagraphLayout, Wrap},
+        Painter, TextPainer,
+    },
+    rect::Rect,
+    text::{Line, Style},
+    widget::StatefulWidget,
+    Simble,
+};
+use crossterm::event::KeyCode;
+use std::{collections::HashSet, hash::Hash};
+
+//nodes of a tree, children are only asked for when their parent is expanded
+pub trait TreeSource: Sync + Send {
+    type Id: Clone + Eq + Hash;
+    fn roots(&self) -> Vec<Self::Id>;
+    fn children(&self, id: &Self::Id) -> Vec<Self::Id>;
+    //decides the marker of a collapsed node without loading its children
+    fn has_children(&self, id: &Self::Id) -> bool;
+    fn label(&self, id: &Self::Id) -> Line;
+}
+//a visible node of the tree
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct FlatNode<Id> {
+    id: Id,
+    parent: Option<usize>,
+    expandable: bool,
+    //for every ancestor level, whether a guide goes down to a later sibling
+    guides: Vec<bool>,
+}
+//expanded nodes and selection of a tree, the visible nodes are cached between renders
+#[derive(Clone, Debug)]
+pub struct TreeState<Id> {
+    list: ListState,
+    expanded: HashSet<Id>,
+    nodes: Vec<FlatNode<Id>>,
+    //the nodes have to be walked again on the next render
+    stale: bool,
+}
+impl<Id> Default for TreeState<Id> {
+    fn default() -> Self {
+        Self {
+            list: ListState::default(),
+            expanded: HashSet::new(),
+            nodes: Vec::new(),
+            stale: true,
+        }
+    }
+}
+impl<Id: Clone + Eq + Hash> TreeState<Id> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn is_expanded(&self, id: &Id) -> bool {
+        self.expanded.contains(id)
+    }
+    pub fn expand(&mut self, id: Id) {
+        self.stale |= self.expanded.insert(id);
+    }
+    pub fn collapse(&mut self, id: &Id) {
+        self.stale |= self.expanded.remove(id);
+    }
+    pub fn toggle(&mut self, id: Id) {
+        if self.is_expanded(&id) {
+            self.collapse(&id);
+        } else {
+            self.expand(id);
+        }
+    }
+    //walks the source again on the next render, for sources whose nodes changed
+    pub fn refresh(&mut self) {
+        self.stale = true;
+    }
+    //selection and scroll over the visible nodes
+    pub fn list(&self) -> &ListState {
+        &self.list
+    }
+    pub fn list_mut(&mut self) -> &mut ListState {
+        &mut self.list
+    }
+    //the selected node as of the last render
+    pub fn selected(&self) -> Option<&Id> {
+        self.selected_node().map(|node| &node.id)
+    }
+    //number of visible nodes as of the last render
+    pub fn visible_len(&self) -> usize {
+        self.nodes.len()
+    }
+    fn selected_node(&self) -> Option<&FlatNode<Id>> {
+        self.nodes.get(self.list.selected()?)
+    }
+    //lists the nodes whose ancestors are all expanded, depth first
+    fn flatten<S: TreeSource<Id = Id>>(&mut self, source: &S) {
+        let mut nodes = Vec::new();
+        //children left to visit at every level, in reverse order
+        let mut stack: Vec<(Vec<Id>, Option<usize>)> = Vec::new();
+        let mut roots = source.roots();
+        roots.reverse();
+        stack.push((roots, None));
+        while let Some((siblings, parent)) = stack.last_mut() {
+            let Some(id) = siblings.pop() else {
+                stack.pop();
+                continue;
+            };
+            let parent = *parent;
+            let ancestors = &stack[..stack.len() - 1];
+            let guides = ancestors.iter().map(|(s, _)| !s.is_empty()).collect();
+            let expandable = source.has_children(&id);
+            let index = nodes.len();
+            if expandable && self.expanded.contains(&id) {
+                let mut children = source.children(&id);
+                children.reverse();
+                stack.push((children, Some(index)));
+            }
+            nodes.push(FlatNode {
+                id,
+                parent,
+                expandable,
+                guides,
+            });
+        }
+        //the selection follows its node, or its nearest ancestor if it was hidden
+        let mut selected = self.list.selected().filter(|&i| i < self.nodes.len());
+        while let Some(old) = selected {
+            let id = &self.nodes[old].id;
+            if let Some(index) = nodes.iter().position(|n| &n.id == id) {
+                self.list.select(Some(index));
+                break;
+            }
+            selected = self.nodes[old].parent;
+        }
+        self.nodes = nodes;
+        self.stale = false;
+    }
+}
+//arrows move the selection, right expands or goes to the first child, left collapses or
+//goes to the parent, enter and space toggle the selected node
+impl<Id: Clone + Eq + Hash> InputHandler for TreeState<Id> {
+    fn handle_event(&mut self, event: &InputEvent) -> EventResult {
+        let code = event.pressed_key().map(|key| key.code);
+        let Some(node) = self.selected_node().cloned() else {
+            return self.list.handle_event(event);
+        };
+        let expanded = self.is_expanded(&node.id);
+        match code {
+            Some(KeyCode::Right) if node.expandable && !expanded => self.expand(node.id),
+            Some(KeyCode::Right) if node.expandable => self.list.select_next(),
+            Some(KeyCode::Left) if expanded => self.collapse(&node.id),
+            Some(KeyCode::Left) if node.parent.is_some() => self.list.select(node.parent),
+            Some(KeyCode::Enter | KeyCode::Char(' ')) if node.expandable => self.toggle(node.id),
+            _ => return self.list.handle_event(event),
+        }
+        EventResult::Consumed
+    }
+}
+//a tree drawn one node per row, only the visible rows of expanded nodes are rendered
+pub struct Tree<S> {
+    source: S,
+    expanded_symbol: Simble,
+    collapsed_symbol: Simble,
+    guide_symbol: Simble,
+    guide_style: Style,
+    highlight_style: Style,
+}
+impl<S> Tree<S> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            expanded_symbol: '▾'.into(),
+            collapsed_symbol: '▸'.into(),
+            guide_symbol: '│'.into(),
+            guide_style: Style::new(),
+            highlight_style: Style::new(),
+        }
+    }
+    pub fn expanded_symbol(mut self, symbol: impl Into<Simble>) -> Self {
+        self.expanded_symbol = symbol.into();
+        self
+    }
+    pub fn collapsed_symbol(mut self, symbol: impl Into<Simble>) -> Self {
+        self.collapsed_symbol = symbol.into();
+        self
+    }
+    //drawn in the indent of the nodes that have a later sibling above them
+    pub fn guide_symbol(mut self, symbol: impl Into<Simble>) -> Self {
+        self.guide_symbol = symbol.into();
+        self
+    }
+    pub fn guide_style(mut self, style: Style) -> Self {
+        self.guide_style = style;
+        self
+    }
+    //painted over the selected row
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+}
+impl<P: Painter, S: TreeSource> StatefulWidget<P> for Tree<S> {
+    type State = TreeState<S::Id>;
+    fn render_stateful_widget(&self, painter: &mut P, state: &mut TreeState<S::Id>) {
+        if state.stale {
+            state.flatten(&self.source);
+        }
+        let area = painter.area();
+        let len = state.nodes.len();
+        state.list.clamp_selection(len);
+        let offset = visible_offset(&state.list, len, area.height, |_| 1);
+        state.list.set_offset(offset);
+        let end = len.min(offset + area.height as usize);
+        state.list.set_page(end - offset, 1, 0);
+        let label_layout = ParagraphLayout::new().wrap(Wrap::None).ellipsis('…');
+        for (y, index) in (offset..end).enumerate() {
+            let node = &state.nodes[index];
+            let y = y as u16;
+            //two columns per level, the guide and a space
+            let mut prefix = Vec::with_capacity(node.guides.len() * 2 + 2);
+            for &guide in &node.guides {
+                prefix.push(if guide {
+                    self.guide_symbol.clone()
+                } else {
+                    Simble::default()
+                });
+                prefix.push(Simble::default());
+            }
+            let guides_width = prefix.len() as u16;
+            prefix.push(match node.expandable {
+                true if state.expanded.contains(&node.id) => self.expanded_symbol.clone(),
+                true => self.collapsed_symbol.clone(),
+                false => Simble::default(),
+            });
+            prefix.push(Simble::default());
+            let prefix_width = prefix.len() as u16;
+            painter.write_simbles(&prefix, Rect::new(0, y, prefix_width, 1));
+            self.guide_style
+                .fill(painter, Some(Rect::new(0, y, guides_width, 1)));
+            let label = Rect::new(prefix_width, y, area.width.saturating_sub(prefix_width), 1);
+            painter.write_text(&self.source.label(&node.id).into(), label, &label_layout);
+            if state.list.selected() == Some(index) {
+                self.highlight_style
+                    .fill(painter, Some(Rect::new(0, y, area.width, 1)));
+            }
+        }
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::test_backend::{left_click, TB};
+    use crossterm::event::{KeyEvent, KeyModifiers};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    //paths separated by slashes, counting how many times children are loaded
+    struct Files {
+        paths: Vec<&'static str>,
+        loads: AtomicUsize,
+    }
+    impl Files {
+        fn new() -> Self {
+            Self {
+                paths: vec![
+                    "src",
+                    "src/app",
+                    "src/app/main",
+                    "src/lib",
+                    "docs",
+                    "docs/a",
+                ],
+                loads: AtomicUsize::new(0),
+            }
+        }
+        fn under(&self, parent: &str) -> Vec<&'static str> {
+            let depth = parent.matches('/').count() + 1;
+            self.paths
+                .iter()
+                .copied()
+                .filter(|p| {
+                    p.starts_with(&format!("{}/", parent)) && p.matches('/').count() == depth
+                })
+                .collect()
+        }
+    }
+    impl TreeSource for Files {
+        type Id = &'static str;
+        fn roots(&self) -> Vec<&'static str> {
+            self.paths
+                .iter()
+                .copied()
+                .filter(|p| !p.contains('/'))
+                .collect()
+        }
+        fn children(&self, id: &&'static str) -> Vec<&'static str> {
+            self.loads.fetch_add(1, Ordering::SeqCst);
+            self.under(id)
+        }
+        fn has_children(&self, id: &&'static str) -> bool {
+            !self.under(id).is_empty()
+        }
+        fn label(&self, id: &&'static str) -> Line {
+            id.rsplit('/').next().unwrap_or(id).into()
+        }
+    }
+    fn press(state: &mut TreeState<&'static str>, code: KeyCode) {
+        state.handle_event(&KeyEvent::new(code, KeyModifiers::NONE).into());
+    }
+
+    #[test]
+    fn test_lazy_children() {
+        let mut backend = TB::new(10, 4);
+        let tree = Tree::new(Files::new());
+        let mut state = TreeState::new();
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        backend.assert_text(&["▸ src     ", "▸ docs    ", "          ", "          "]);
+        assert_eq!(tree.source().loads.load(Ordering::SeqCst), 0);
+        state.expand("src");
+        state.expand("src/app");
+        backend.reset();
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        backend.assert_text(&["▾ src     ", "│ ▾ app   ", "│ │   main", "│   lib   "]);
+        assert_eq!(tree.source().loads.load(Ordering::SeqCst), 2);
+        //the cache is reused while nothing is expanded or collapsed
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        assert_eq!(tree.source().loads.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn test_keyboard() {
+        let mut backend = TB::new(10, 3);
+        let tree = Tree::new(Files::new());
+        let mut state = TreeState::new();
+        state.list_mut().select(Some(0));
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        press(&mut state, KeyCode::Right);
+        assert!(state.is_expanded(&"src"));
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        press(&mut state, KeyCode::Right);
+        press(&mut state, KeyCode::Down);
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.selected(), Some(&"src/lib"));
+        press(&mut state, KeyCode::Left);
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.selected(), Some(&"src"));
+        press(&mut state, KeyCode::Left);
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.visible_len(), 2);
+        assert_eq!(state.selected(), Some(&"src"));
+    }
+
+    #[test]
+    fn test_collapse_selects_visible_ancestor() {
+        let mut backend = TB::new(10, 4);
+        let tree = Tree::new(Files::new());
+        let mut state = TreeState::new();
+        state.expand("src");
+        state.expand("src/app");
+        state.list_mut().select(Some(2));
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.selected(), Some(&"src/app/main"));
+        state.collapse(&"src");
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.selected(), Some(&"src"));
+    }
+
+    #[test]
+    fn test_click_selects() {
+        let mut backend = TB::new(10, 4);
+        let tree = Tree::new(Files::new());
+        let mut state = TreeState::new();
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.handle_event(&left_click(0, 1)), EventResult::Consumed);
+        assert_eq!(state.selected(), Some(&"docs"));
+        assert_eq!(state.handle_event(&left_click(0, 3)), EventResult::Bubbled);
+    }
+
+    #[test]
+    fn test_renders_only_visible_rows() {
+        let mut backend = TB::new(10, 2);
+        let tree = Tree::new(Files::new());
+        let mut state = TreeState::new();
+        state.expand("src");
+        state.expand("src/app");
+        state.list_mut().select(Some(3));
+        backend.render_stateful_widget(&tree, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.list().offset(), 2);
+        backend.assert_text(&["│ │   main", "│   lib   "]);
+    }
+}
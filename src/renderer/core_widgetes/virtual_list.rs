@@ -0,0 +1,240 @@
+use super::list::ListState;
+use crate::renderer::{
+    painter::Painter,
+    rect::Rect,
+    text::Style,
+    widget::{StatefulWidget, Widget},
+};
+
+//items of a virtual list, only the ones on screen are asked for
+pub trait ListSource: Sync + Send {
+    //a widget for the painters the list is rendered with
+    type Item;
+    fn len(&self) -> usize;
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    fn item(&self, index: usize) -> Self::Item;
+    //rows taken by the item, items are at least one row high
+    fn item_height(&self, _index: usize) -> u16 {
+        1
+    }
+}
+//a list that renders only the items between the offset of its state and the bottom of the
+//area, the cost of a frame doesn't depend on the number of items
+pub struct VirtualList<S> {
+    source: S,
+    highlight_style: Style,
+}
+impl<S> VirtualList<S> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            highlight_style: Style::new(),
+        }
+    }
+    //painted over the selected item
+    pub fn highlight_style(mut self, style: Style) -> Self {
+        self.highlight_style = style;
+        self
+    }
+    pub fn source(&self) -> &S {
+        &self.source
+    }
+    pub fn source_mut(&mut self) -> &mut S {
+        &mut self.source
+    }
+}
+//first item of the page that ends with the given item
+fn page_ending_at(last: usize, height: u16, item_height: &impl Fn(usize) -> u16) -> usize {
+    let mut first = last;
+    let mut used = item_height(last) as u32;
+    while first > 0 {
+        let previous = item_height(first - 1) as u32;
+        if used + previous > height as u32 {
+            break;
+        }
+        used += previous;
+        first -= 1;
+    }
+    first
+}
+//offset that keeps the selection visible and the last page full for items of varying
+//heights, only the heights of the items around the window are asked for
+pub(crate) fn visible_offset(
+    state: &ListState,
+    len: usize,
+    height: u16,
+    item_height: impl Fn(usize) -> u16,
+) -> usize {
+    if len == 0 {
+        return 0;
+    }
+    let item_height = |index| item_height(index).max(1);
+    let last_page = page_ending_at(len - 1, height, &item_height);
+    let offset = state.offset().min(last_page);
+    let Some(selected) = state.selected() else {
+        return offset;
+    };
+    if selected < offset {
+        return selected;
+    }
+    let mut used = 0u32;
+    for index in offset..=selected {
+        used += item_height(index) as u32;
+        if used > height as u32 {
+            return page_ending_at(selected, height, &item_height);
+        }
+    }
+    offset
+}
+impl<P: Painter, S: ListSource> StatefulWidget<P> for VirtualList<S>
+where
+    S::Item: Widget<P>,
+{
+    type State = ListState;
+    fn render_stateful_widget(&self, painter: &mut P, state: &mut ListState) {
+        let area = painter.area();
+        let len = self.source.len();
+        state.clamp_selection(len);
+        if len == 0 || area.height == 0 {
+            state.set_page(0, 0, 0);
+            return;
+        }
+        let offset = visible_offset(state, len, area.height, |i| self.source.item_height(i));
+        state.set_offset(offset);
+        let mut y = 0u16;
+        let mut shown = 0;
+        //clicks map to items only if they all have one row
+        let mut uniform = true;
+        for index in offset..len {
+            if y >= area.height {
+                break;
+            }
+            let height = self.source.item_height(index).max(1);
+            uniform &= height == 1;
+            let row = Rect::new(0, y, area.width, height);
+            painter.render_widget(&self.source.item(index), row, 0, 0);
+            if state.selected() == Some(index) {
+                self.highlight_style.fill(painter, Some(row));
+            }
+            y = y.saturating_add(height);
+            shown += 1;
+        }
+        state.set_page(shown, if uniform { 1 } else { 0 }, 0);
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{
+        input::{EventResult, InputHandler},
+        painter::TextPainer,
+        test_backend::{left_click, TB},
+    };
+    use crossterm::style::Color;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Label(String, u16);
+    impl<P: Painter + TextPainer> Widget<P> for Label {
+        fn render_widget(&self, painter: &mut P) {
+            for y in 0..self.1 {
+                painter.write_text_line(&self.0, 0, y);
+            }
+        }
+    }
+    //numbers, every third one two rows high, counting the items asked for
+    struct Numbers {
+        len: usize,
+        asked: AtomicUsize,
+    }
+    impl Numbers {
+        fn new(len: usize) -> Self {
+            Self {
+                len,
+                asked: AtomicUsize::new(0),
+            }
+        }
+    }
+    impl ListSource for Numbers {
+        type Item = Label;
+        fn len(&self) -> usize {
+            self.len
+        }
+        fn item(&self, index: usize) -> Label {
+            self.asked.fetch_add(1, Ordering::SeqCst);
+            Label(index.to_string(), self.item_height(index))
+        }
+        fn item_height(&self, index: usize) -> u16 {
+            if index % 3 == 2 {
+                2
+            } else {
+                1
+            }
+        }
+    }
+
+    #[test]
+    fn test_renders_only_visible_items() {
+        let mut backend = TB::new(6, 4);
+        let list = VirtualList::new(Numbers::new(100_000));
+        let mut state = ListState::new();
+        state.set_offset(3);
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        backend.assert_text(&["3     ", "4     ", "5     ", "5     "]);
+        assert_eq!(list.source().asked.load(Ordering::SeqCst), 3);
+    }
+
+    #[test]
+    fn test_selection_scrolls() {
+        let mut backend = TB::new(6, 4);
+        let list =
+            VirtualList::new(Numbers::new(100_000)).highlight_style(Style::new().bg(Color::Blue));
+        let mut state = ListState::new().with_selected(Some(usize::MAX));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.selected(), Some(99_999));
+        assert_eq!(state.offset(), 99_997);
+        backend.assert_text(&["99997 ", "99998 ", "99998 ", "99999 "]);
+        assert_eq!(backend.cell(0, 3).background, Color::Blue.into());
+        state.select(Some(10));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.offset(), 10);
+    }
+
+    //numbers one row high
+    struct Rows(usize);
+    impl ListSource for Rows {
+        type Item = Label;
+        fn len(&self) -> usize {
+            self.0
+        }
+        fn item(&self, index: usize) -> Label {
+            Label(index.to_string(), 1)
+        }
+    }
+
+    #[test]
+    fn test_click_selects() {
+        let mut backend = TB::new(3, 4);
+        let list = VirtualList::new(Rows(100));
+        let mut state = ListState::new();
+        state.set_offset(10);
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.handle_event(&left_click(0, 2)), EventResult::Consumed);
+        assert_eq!(state.selected(), Some(12));
+        //items of varying heights can't be clicked
+        let list = VirtualList::new(Numbers::new(100));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.handle_event(&left_click(0, 0)), EventResult::Bubbled);
+    }
+
+    #[test]
+    fn test_empty() {
+        let mut backend = TB::new(2, 2);
+        let list = VirtualList::new(Numbers::new(0));
+        let mut state = ListState::new().with_selected(Some(3));
+        backend.render_stateful_widget(&list, &mut state, backend.area(), 0, 0);
+        assert_eq!(state.selected(), None);
+        backend.assert_text(&["  ", "  "]);
+    }
+}
@@ -0,0 +1,131 @@
+use super::{buffer_mediator::BufferMediator, rect::Rect};
+
+//identifier given to a widget so it can be recognized in hit tests
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct WidgetId(pub u64);
+impl From<u64> for WidgetId {
+    fn from(id: u64) -> Self {
+        Self(id)
+    }
+}
+//widget found under a point of the screen
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Hit {
+    pub id: Option<WidgetId>,
+    //area the widget was rendered to, in the space of its parent
+    pub area: Rect,
+    //part of the screen the widget is visible in
+    pub screen_area: Rect,
+    //position of the point in the space of the widget, scroll included
+    pub x: u16,
+    pub y: u16,
+    //number of widgets the widget is rendered in
+    pub depth: u16,
+}
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    id: Option<WidgetId>,
+    area: Rect,
+    mediator: BufferMediator,
+    depth: u16,
+}
+//areas of the widgets rendered in a frame, recorded in the order they were rendered
+#[derive(Clone, Debug, Default)]
+pub struct HitMap {
+    entries: Vec<Entry>,
+    depth: u16,
+}
+impl HitMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.depth = 0;
+    }
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+    //called by painters before rendering a widget, with the mediator it is rendered through
+    pub fn enter(&mut self, id: Option<WidgetId>, area: Rect, mediator: BufferMediator) {
+        self.entries.push(Entry {
+            id,
+            area,
+            mediator,
+            depth: self.depth,
+        });
+        self.depth += 1;
+    }
+    //called by painters once the widget is rendered
+    pub fn leave(&mut self) {
+        self.depth = self.depth.saturating_sub(1);
+    }
+    //the innermost widget under the point, children are rendered after their parent and
+    //later widgets are drawn over earlier ones so the last match wins
+    pub fn hit_test(&self, x: u16, y: u16) -> Option<Hit> {
+        self.hits(x, y).next()
+    }
+    //every widget under the point, from the one drawn on top to the one drawn first
+    pub fn hits(&self, x: u16, y: u16) -> impl Iterator<Item = Hit> + '_ {
+        self.entries.iter().rev().filter_map(move |entry| {
+            let (local_x, local_y) = entry.mediator.to_local(x, y)?;
+            Some(Hit {
+                id: entry.id,
+                area: entry.area,
+                screen_area: entry.mediator.screen_area(),
+                x: local_x,
+                y: local_y,
+                depth: entry.depth,
+            })
+        })
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_innermost_wins() {
+        let mut map = HitMap::new();
+        let root = BufferMediator::new(Rect::new(0, 0, 20, 10), 0, 0);
+        let parent_area = Rect::new(2, 2, 10, 5);
+        let parent = root.generate_inner(&parent_area, 0, 4);
+        map.enter(Some(1.into()), parent_area, parent);
+        let child_area = Rect::new(1, 5, 3, 2);
+        map.enter(
+            Some(2.into()),
+            child_area,
+            parent.generate_inner(&child_area, 0, 0),
+        );
+        map.leave();
+        map.leave();
+        let hit = map.hit_test(4, 3).unwrap();
+        assert_eq!(hit.id, Some(WidgetId(2)));
+        assert_eq!((hit.x, hit.y), (1, 0));
+        assert_eq!(hit.depth, 1);
+        assert_eq!(hit.screen_area, Rect::new(3, 3, 3, 2));
+        //outside the child the parent is hit, with its scroll offset
+        let hit = map.hit_test(10, 6).unwrap();
+        assert_eq!(hit.id, Some(WidgetId(1)));
+        assert_eq!((hit.x, hit.y), (8, 8));
+        assert_eq!(map.hits(4, 3).count(), 2);
+        assert_eq!(map.hit_test(0, 0), None);
+    }
+
+    #[test]
+    fn test_later_siblings_are_on_top() {
+        let mut map = HitMap::new();
+        let root = BufferMediator::new(Rect::new(0, 0, 10, 10), 0, 0);
+        for (id, area) in [(1, Rect::new(0, 0, 4, 4)), (2, Rect::new(2, 2, 4, 4))] {
+            map.enter(Some(id.into()), area, root.generate_inner(&area, 0, 0));
+            map.leave();
+        }
+        assert_eq!(map.hit_test(3, 3).unwrap().id, Some(WidgetId(2)));
+        assert_eq!(map.hit_test(1, 1).unwrap().id, Some(WidgetId(1)));
+        map.clear();
+        assert!(map.is_empty());
+    }
+}
@@ -0,0 +1,318 @@
+use super::{hit_test::Hit, hit_test::WidgetId, painter::Painter, rect::Rect, text::Style};
+use crossterm::event::{KeyCode, KeyEvent, KeyEventKind, MouseEvent, MouseEventKind};
+
+//input given to a widget
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputEvent {
+    Key(KeyEvent),
+    //column and row are in the space of the widget, scroll included
+    Mouse(MouseEvent),
+    Paste(String),
+}
+impl InputEvent {
+    //a mouse event of the terminal moved to the space of the widget that was hit
+    pub fn mouse(event: MouseEvent, hit: &Hit) -> Self {
+        InputEvent::Mouse(MouseEvent {
+            column: hit.x,
+            row: hit.y,
+            ..event
+        })
+    }
+    //the mouse wheel as a number of lines, down is positive
+    pub fn wheel_delta(&self) -> Option<i32> {
+        match self {
+            InputEvent::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollDown,
+                ..
+            }) => Some(1),
+            InputEvent::Mouse(MouseEvent {
+                kind: MouseEventKind::ScrollUp,
+                ..
+            }) => Some(-1),
+            _ => None,
+        }
+    }
+    //key presses and repeats, releases are reported by some terminals only
+    pub fn pressed_key(&self) -> Option<KeyEvent> {
+        match self {
+            InputEvent::Key(key) if key.kind != KeyEventKind::Release => Some(*key),
+            _ => None,
+        }
+    }
+}
+impl From<KeyEvent> for InputEvent {
+    fn from(key: KeyEvent) -> Self {
+        InputEvent::Key(key)
+    }
+}
+//whether a widget used an event or lets its parent handle it
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EventResult {
+    Consumed,
+    Bubbled,
+}
+impl EventResult {
+    pub fn is_consumed(self) -> bool {
+        self == EventResult::Consumed
+    }
+    //the other handler only gets a chance if the event bubbled
+    pub fn or_else(self, f: impl FnOnce() -> EventResult) -> EventResult {
+        match self {
+            EventResult::Consumed => EventResult::Consumed,
+            EventResult::Bubbled => f(),
+        }
+    }
+}
+pub trait InputHandler {
+    fn handle_event(&mut self, event: &InputEvent) -> EventResult;
+}
+//an input handler that can take the keyboard focus
+pub trait Focusable: InputHandler {
+    fn focus_id(&self) -> WidgetId;
+    //called by the focus manager when the widget gains or loses the focus
+    fn on_focus_change(&mut self, _focused: bool) {}
+}
+//keeps track of the focused widget and moves the focus with tab and shift tab
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FocusManager {
+    //traversal order of the widgets that can be focused
+    order: Vec<WidgetId>,
+    focused: Option<WidgetId>,
+    //focus the widgets were last told about
+    notified: Option<WidgetId>,
+}
+impl FocusManager {
+    //the first widget of the order gets the focus
+    pub fn new(order: impl Into<Vec<WidgetId>>) -> Self {
+        let order = order.into();
+        Self {
+            focused: order.first().copied(),
+            order,
+            notified: None,
+        }
+    }
+    pub fn order(&self) -> &[WidgetId] {
+        &self.order
+    }
+    //the focus stays where it is if the widget is still in the order
+    pub fn set_order(&mut self, order: impl Into<Vec<WidgetId>>) {
+        self.order = order.into();
+        if !self.focused.is_some_and(|id| self.order.contains(&id)) {
+            self.focused = self.order.first().copied();
+        }
+    }
+    pub fn focused(&self) -> Option<WidgetId> {
+        self.focused
+    }
+    pub fn is_focused(&self, id: WidgetId) -> bool {
+        self.focused == Some(id)
+    }
+    //returns false if the widget is not in the order
+    pub fn focus(&mut self, id: WidgetId) -> bool {
+        let found = self.order.contains(&id);
+        if found {
+            self.focused = Some(id);
+        }
+        found
+    }
+    pub fn blur(&mut self) {
+        self.focused = None;
+    }
+    pub fn focus_next(&mut self) {
+        self.step(1);
+    }
+    pub fn focus_previous(&mut self) {
+        self.step(self.order.len().saturating_sub(1));
+    }
+    fn step(&mut self, steps: usize) {
+        if self.order.is_empty() {
+            return;
+        }
+        let next = match self
+            .focused
+            .and_then(|id| self.order.iter().position(|&o| o == id))
+        {
+            Some(position) => (position + steps) % self.order.len(),
+            //without a focus tab goes to the first widget and shift tab to the last
+            None if steps == 1 => 0,
+            None => self.order.len() - 1,
+        };
+        self.focused = Some(self.order[next]);
+    }
+    //focuses the innermost widget under a click that can take the focus
+    pub fn focus_hit<'a>(&mut self, hits: impl IntoIterator<Item = &'a Hit>) -> bool {
+        let target = hits
+            .into_iter()
+            .filter_map(|hit| hit.id)
+            .find(|id| self.order.contains(id));
+        target.is_some_and(|id| self.focus(id))
+    }
+    //tells the widgets that gained or lost the focus since the last time
+    pub fn notify(&mut self, widgets: &mut [&mut dyn Focusable]) {
+        if self.notified == self.focused {
+            return;
+        }
+        for widget in widgets.iter_mut() {
+            let id = widget.focus_id();
+            if Some(id) == self.notified {
+                widget.on_focus_change(false);
+            } else if Some(id) == self.focused {
+                widget.on_focus_change(true);
+            }
+        }
+        self.notified = self.focused;
+    }
+    //gives the event to the focused widget, tab and shift tab move the focus if it bubbles
+    pub fn dispatch(
+        &mut self,
+        event: &InputEvent,
+        widgets: &mut [&mut dyn Focusable],
+    ) -> EventResult {
+        self.notify(widgets);
+        let focused = widgets
+            .iter_mut()
+            .find(|widget| Some(widget.focus_id()) == self.focused);
+        let result = match focused {
+            Some(widget) => widget.handle_event(event),
+            None => EventResult::Bubbled,
+        };
+        let result = result.or_else(|| self.handle_event(event));
+        self.notify(widgets);
+        result
+    }
+}
+impl InputHandler for FocusManager {
+    fn handle_event(&mut self, event: &InputEvent) -> EventResult {
+        match event.pressed_key().map(|key| key.code) {
+            Some(KeyCode::Tab) => self.focus_next(),
+            Some(KeyCode::BackTab) => self.focus_previous(),
+            _ => return EventResult::Bubbled,
+        }
+        EventResult::Consumed
+    }
+}
+//styles painted over a widget depending on whether it has the focus
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FocusStyle {
+    pub focused: Style,
+    pub unfocused: Style,
+}
+impl FocusStyle {
+    pub fn new(focused: Style, unfocused: Style) -> Self {
+        Self { focused, unfocused }
+    }
+    pub fn style(&self, focused: bool) -> Style {
+        if focused {
+            self.focused
+        } else {
+            self.unfocused
+        }
+    }
+    //fills the area, or the whole painter, with the parts of the style that are set
+    pub fn apply<P: Painter>(&self, painter: &mut P, focused: bool, area: Option<Rect>) {
+        self.style(focused).fill(painter, area);
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{test_backend::TB, BackgroundColor};
+    use crossterm::{event::KeyModifiers, style::Color};
+
+    struct Input {
+        id: WidgetId,
+        text: String,
+        focused: bool,
+    }
+    impl Input {
+        fn new(id: u64) -> Self {
+            Self {
+                id: WidgetId(id),
+                text: String::new(),
+                focused: false,
+            }
+        }
+    }
+    impl InputHandler for Input {
+        fn handle_event(&mut self, event: &InputEvent) -> EventResult {
+            match event.pressed_key().map(|key| key.code) {
+                Some(KeyCode::Char(c)) => {
+                    self.text.push(c);
+                    EventResult::Consumed
+                }
+                _ => EventResult::Bubbled,
+            }
+        }
+    }
+    impl Focusable for Input {
+        fn focus_id(&self) -> WidgetId {
+            self.id
+        }
+        fn on_focus_change(&mut self, focused: bool) {
+            self.focused = focused;
+        }
+    }
+    fn key(code: KeyCode) -> InputEvent {
+        KeyEvent::new(code, KeyModifiers::NONE).into()
+    }
+
+    #[test]
+    fn test_traversal() {
+        let mut focus = FocusManager::new([WidgetId(1), WidgetId(2), WidgetId(3)]);
+        assert_eq!(focus.focused(), Some(WidgetId(1)));
+        focus.focus_previous();
+        assert_eq!(focus.focused(), Some(WidgetId(3)));
+        focus.focus_next();
+        assert_eq!(focus.focused(), Some(WidgetId(1)));
+        assert!(!focus.focus(WidgetId(7)));
+        focus.blur();
+        focus.focus_previous();
+        assert_eq!(focus.focused(), Some(WidgetId(3)));
+        focus.set_order([WidgetId(2)]);
+        assert_eq!(focus.focused(), Some(WidgetId(2)));
+    }
+
+    #[test]
+    fn test_dispatch() {
+        let mut first = Input::new(1);
+        let mut second = Input::new(2);
+        let mut focus = FocusManager::new([WidgetId(1), WidgetId(2)]);
+        let result = focus.dispatch(&key(KeyCode::Char('a')), &mut [&mut first, &mut second]);
+        assert_eq!(result, EventResult::Consumed);
+        assert!(first.focused);
+        let result = focus.dispatch(&key(KeyCode::Tab), &mut [&mut first, &mut second]);
+        assert_eq!(result, EventResult::Consumed);
+        assert!(!first.focused && second.focused);
+        focus.dispatch(&key(KeyCode::Char('b')), &mut [&mut first, &mut second]);
+        assert_eq!((first.text.as_str(), second.text.as_str()), ("a", "b"));
+        let result = focus.dispatch(&key(KeyCode::Enter), &mut [&mut first, &mut second]);
+        assert_eq!(result, EventResult::Bubbled);
+    }
+
+    #[test]
+    fn test_focus_hit() {
+        let mut focus = FocusManager::new([WidgetId(1), WidgetId(2)]);
+        let hit = |id: Option<u64>| Hit {
+            id: id.map(WidgetId),
+            area: Rect::new(0, 0, 1, 1),
+            screen_area: Rect::new(0, 0, 1, 1),
+            x: 0,
+            y: 0,
+            depth: 0,
+        };
+        assert!(focus.focus_hit(&[hit(None), hit(Some(2)), hit(Some(1))]));
+        assert_eq!(focus.focused(), Some(WidgetId(2)));
+        assert!(!focus.focus_hit(&[hit(Some(5))]));
+    }
+
+    #[test]
+    fn test_focus_style() {
+        let style = FocusStyle::new(Style::new().bg(Color::Blue), Style::new());
+        let mut backend = TB::new(3, 1);
+        style.apply(&mut backend, false, None);
+        assert_eq!(backend.cell(0, 0).background, BackgroundColor::default());
+        style.apply(&mut backend, true, Some(Rect::new(1, 0, 1, 1)));
+        assert_eq!(backend.cell(1, 0).background, Color::Blue.into());
+        assert_eq!(backend.cell(2, 0).background, BackgroundColor::default());
+    }
+}
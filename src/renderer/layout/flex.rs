@@ -0,0 +1,255 @@
+use super::{split_evenly, Align, Justify, Orientation};
+use crate::renderer::rect::Rect;
+
+//size of an item along the main axis of the flex, and across it if it has one
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct FlexItem {
+    pub main: u16,
+    pub cross: Option<u16>,
+    //share of the free space of the line the item takes, 0 keeps its size
+    pub grow: u16,
+}
+impl FlexItem {
+    pub fn new(main: u16) -> Self {
+        Self {
+            main,
+            cross: None,
+            grow: 0,
+        }
+    }
+    pub fn cross(mut self, cross: u16) -> Self {
+        self.cross = Some(cross);
+        self
+    }
+    pub fn grow(mut self, grow: u16) -> Self {
+        self.grow = grow;
+        self
+    }
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Flex {
+    pub orientation: Orientation,
+    //start a new line when the next item doesn't fit
+    pub wrap: bool,
+    //space between items of a line
+    pub gap: u16,
+    //space between lines
+    pub line_gap: u16,
+    pub justify: Justify,
+    pub align: Align,
+}
+impl Flex {
+    pub fn new(orientation: Orientation) -> Self {
+        Self {
+            orientation,
+            ..Default::default()
+        }
+    }
+    pub fn horizontal() -> Self {
+        Self::new(Orientation::Horizontal)
+    }
+    pub fn vertical() -> Self {
+        Self::new(Orientation::Vertical)
+    }
+    pub fn wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.gap = gap;
+        self
+    }
+    pub fn line_gap(mut self, gap: u16) -> Self {
+        self.line_gap = gap;
+        self
+    }
+    pub fn justify(mut self, justify: Justify) -> Self {
+        self.justify = justify;
+        self
+    }
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+    //an item of the given size on screen, whatever the orientation
+    pub fn item(&self, width: u16, height: u16) -> FlexItem {
+        match self.orientation {
+            Orientation::Horizontal => FlexItem::new(width).cross(height),
+            Orientation::Vertical => FlexItem::new(height).cross(width),
+        }
+    }
+    //one rect per item, in the order of the items, clipped to the area
+    pub fn split(&self, area: Rect, items: &[FlexItem]) -> Vec<Rect> {
+        let (main_start, cross_start, main_lenght, cross_lenght) = match self.orientation {
+            Orientation::Horizontal => (area.x, area.y, area.width, area.height),
+            Orientation::Vertical => (area.y, area.x, area.height, area.width),
+        };
+        let lines = self.lines(items, main_lenght);
+        let line_sizes = self.line_sizes(&lines, items, cross_lenght);
+        let mut rects = vec![Rect::new(area.x, area.y, 0, 0); items.len()];
+        let mut cross_position = cross_start as u32;
+        for (line, line_size) in lines.iter().zip(line_sizes) {
+            let mut sizes: Vec<u16> = line
+                .iter()
+                .map(|&i| items[i].main.min(main_lenght))
+                .collect();
+            let gaps = self.gap as u32 * (line.len() as u32 - 1);
+            let used = sizes.iter().map(|&s| s as u32).sum::<u32>() + gaps;
+            let mut free = (main_lenght as u32).saturating_sub(used) as u16;
+            let total_grow: u32 = line.iter().map(|&i| items[i].grow as u32).sum();
+            //None when no item grows and the free space is left to the justification
+            let shares: Option<Vec<u32>> = line
+                .iter()
+                .map(|&i| (free as u32 * items[i].grow as u32).checked_div(total_grow))
+                .collect();
+            if let Some(shares) = shares {
+                let mut given = 0;
+                for (size, share) in sizes.iter_mut().zip(shares) {
+                    let share = share as u16;
+                    *size += share;
+                    given += share;
+                }
+                //rounding leftovers go to the first growing items
+                let growing = line.iter().enumerate().filter(|(_, &i)| items[i].grow > 0);
+                for (position, _) in growing.take((free - given) as usize) {
+                    sizes[position] += 1;
+                }
+                free = 0;
+            }
+            let (start, after) = self.justify.distribute(free, line.len());
+            let mut main_position = main_start as u32 + start as u32;
+            for ((&i, size), space) in line.iter().zip(sizes).zip(after) {
+                let (offset, cross) = self.align.place(items[i].cross, line_size);
+                let main = main_position.min(u16::MAX as u32) as u16;
+                let cross_offset = (cross_position + offset as u32).min(u16::MAX as u32) as u16;
+                let rect = match self.orientation {
+                    Orientation::Horizontal => Rect::new(main, cross_offset, size, cross),
+                    Orientation::Vertical => Rect::new(cross_offset, main, cross, size),
+                };
+                rects[i] = area.crop(&rect);
+                main_position += size as u32 + space as u32 + self.gap as u32;
+            }
+            cross_position += line_size as u32 + self.line_gap as u32;
+        }
+        rects
+    }
+    //indexes of the items of every line
+    fn lines(&self, items: &[FlexItem], main_lenght: u16) -> Vec<Vec<usize>> {
+        let mut lines: Vec<Vec<usize>> = Vec::new();
+        let mut used = 0u32;
+        for (i, item) in items.iter().enumerate() {
+            let size = item.main.min(main_lenght) as u32;
+            match lines.last_mut() {
+                Some(line) if !self.wrap || used + self.gap as u32 + size <= main_lenght as u32 => {
+                    line.push(i);
+                    used += self.gap as u32 + size;
+                }
+                _ => {
+                    lines.push(vec![i]);
+                    used = size;
+                }
+            }
+        }
+        lines
+    }
+    //a single line takes the whole area, wrapped lines are as thick as their thickest item
+    //and lines where no item has a cross size share the space left by the others
+    fn line_sizes(&self, lines: &[Vec<usize>], items: &[FlexItem], cross_lenght: u16) -> Vec<u16> {
+        if !self.wrap {
+            return vec![cross_lenght; lines.len()];
+        }
+        let fixed: Vec<Option<u16>> = lines
+            .iter()
+            .map(|line| line.iter().filter_map(|&i| items[i].cross).max())
+            .collect();
+        let gaps = self.line_gap as u32 * (lines.len() as u32).saturating_sub(1);
+        let used = fixed.iter().flatten().map(|&s| s as u32).sum::<u32>() + gaps;
+        let free = (cross_lenght as u32).saturating_sub(used) as u16;
+        let mut shares =
+            split_evenly(free, fixed.iter().filter(|f| f.is_none()).count()).into_iter();
+        fixed
+            .into_iter()
+            .map(|size| size.unwrap_or_else(|| shares.next().unwrap_or(0)))
+            .collect()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        let flex = Flex::horizontal().wrap(true).gap(1).line_gap(1);
+        let items = vec![flex.item(4, 2); 5];
+        assert_eq!(
+            flex.split(Rect::new(0, 0, 14, 10), &items),
+            vec![
+                Rect::new(0, 0, 4, 2),
+                Rect::new(5, 0, 4, 2),
+                Rect::new(10, 0, 4, 2),
+                Rect::new(0, 3, 4, 2),
+                Rect::new(5, 3, 4, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_no_wrap_clips() {
+        let flex = Flex::horizontal();
+        let items = vec![FlexItem::new(4); 3];
+        assert_eq!(
+            flex.split(Rect::new(0, 0, 10, 2), &items),
+            vec![
+                Rect::new(0, 0, 4, 2),
+                Rect::new(4, 0, 4, 2),
+                Rect::new(8, 0, 2, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_justify() {
+        let items = vec![FlexItem::new(2); 3];
+        let area = Rect::new(0, 0, 12, 1);
+        let xs = |justify| -> Vec<u16> {
+            Flex::horizontal()
+                .justify(justify)
+                .split(area, &items)
+                .iter()
+                .map(|r| r.x)
+                .collect()
+        };
+        assert_eq!(xs(Justify::Start), vec![0, 2, 4]);
+        assert_eq!(xs(Justify::Center), vec![3, 5, 7]);
+        assert_eq!(xs(Justify::End), vec![6, 8, 10]);
+        assert_eq!(xs(Justify::SpaceBetween), vec![0, 5, 10]);
+        assert_eq!(xs(Justify::SpaceAround), vec![1, 5, 9]);
+        assert_eq!(xs(Justify::SpaceEvenly), vec![2, 6, 9]);
+    }
+
+    #[test]
+    fn test_grow() {
+        let flex = Flex::vertical();
+        let items = [
+            FlexItem::new(1),
+            FlexItem::new(1).grow(1),
+            FlexItem::new(1).grow(2),
+        ];
+        let heights: Vec<u16> = flex
+            .split(Rect::new(0, 0, 3, 10), &items)
+            .iter()
+            .map(|r| r.height)
+            .collect();
+        assert_eq!(heights, vec![1, 4, 5]);
+    }
+
+    #[test]
+    fn test_align() {
+        let flex = Flex::horizontal().align(Align::Center);
+        let items = [FlexItem::new(2).cross(2), FlexItem::new(2)];
+        assert_eq!(
+            flex.split(Rect::new(0, 0, 4, 6), &items),
+            vec![Rect::new(0, 2, 2, 2), Rect::new(2, 0, 2, 6)]
+        );
+    }
+}
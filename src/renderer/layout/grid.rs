@@ -0,0 +1,265 @@
+use super::{Align, Constraint, Layout};
+use crate::renderer::rect::Rect;
+
+//an item placed on the grid, missing coordinates are chosen by auto placement
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridItem {
+    pub column: Option<u16>,
+    pub row: Option<u16>,
+    pub column_span: u16,
+    pub row_span: u16,
+    //size of the item inside its cells, None stretches it to the cells
+    pub width: Option<u16>,
+    pub height: Option<u16>,
+}
+impl Default for GridItem {
+    fn default() -> Self {
+        Self {
+            column: None,
+            row: None,
+            column_span: 1,
+            row_span: 1,
+            width: None,
+            height: None,
+        }
+    }
+}
+impl GridItem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn at(mut self, column: u16, row: u16) -> Self {
+        self.column = Some(column);
+        self.row = Some(row);
+        self
+    }
+    pub fn column(mut self, column: u16) -> Self {
+        self.column = Some(column);
+        self
+    }
+    pub fn row(mut self, row: u16) -> Self {
+        self.row = Some(row);
+        self
+    }
+    pub fn span(mut self, columns: u16, rows: u16) -> Self {
+        self.column_span = columns.max(1);
+        self.row_span = rows.max(1);
+        self
+    }
+    pub fn size(mut self, width: u16, height: u16) -> Self {
+        self.width = Some(width);
+        self.height = Some(height);
+        self
+    }
+}
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid {
+    pub columns: Vec<Constraint>,
+    pub rows: Vec<Constraint>,
+    //constraint of the rows added when items don't fit in the template
+    pub auto_row: Constraint,
+    pub column_gap: u16,
+    pub row_gap: u16,
+    pub justify_items: Align,
+    pub align_items: Align,
+}
+impl Grid {
+    pub fn new(columns: impl Into<Vec<Constraint>>, rows: impl Into<Vec<Constraint>>) -> Self {
+        Self {
+            columns: columns.into(),
+            rows: rows.into(),
+            auto_row: Constraint::Fill(1),
+            column_gap: 0,
+            row_gap: 0,
+            justify_items: Align::default(),
+            align_items: Align::default(),
+        }
+    }
+    pub fn gap(mut self, gap: u16) -> Self {
+        self.column_gap = gap;
+        self.row_gap = gap;
+        self
+    }
+    pub fn column_gap(mut self, gap: u16) -> Self {
+        self.column_gap = gap;
+        self
+    }
+    pub fn row_gap(mut self, gap: u16) -> Self {
+        self.row_gap = gap;
+        self
+    }
+    pub fn auto_row(mut self, constraint: Constraint) -> Self {
+        self.auto_row = constraint;
+        self
+    }
+    pub fn justify_items(mut self, align: Align) -> Self {
+        self.justify_items = align;
+        self
+    }
+    pub fn align_items(mut self, align: Align) -> Self {
+        self.align_items = align;
+        self
+    }
+    //every cell of the template, row by row
+    pub fn cells(&self, area: Rect) -> Vec<Rect> {
+        let items = vec![GridItem::new(); self.columns.len() * self.rows.len()];
+        self.split(area, &items)
+    }
+    //one rect per item, in the order of the items
+    pub fn split(&self, area: Rect, items: &[GridItem]) -> Vec<Rect> {
+        if self.columns.is_empty() {
+            return vec![Rect::new(area.x, area.y, 0, 0); items.len()];
+        }
+        let placements = self.place(items);
+        let row_count = placements
+            .iter()
+            .map(|p| p.1 + p.3)
+            .max()
+            .unwrap_or(0)
+            .max(self.rows.len() as u16);
+        let mut rows = self.rows.clone();
+        rows.resize(row_count as usize, self.auto_row);
+        let columns = Layout::horizontal(self.columns.clone())
+            .spacing(self.column_gap)
+            .split(area);
+        let rows = Layout::vertical(rows).spacing(self.row_gap).split(area);
+        placements
+            .into_iter()
+            .zip(items)
+            .map(|((column, row, column_span, row_span), item)| {
+                let first_column = columns[column as usize];
+                let last_column = columns[(column + column_span - 1) as usize];
+                let first_row = rows[row as usize];
+                let last_row = rows[(row + row_span - 1) as usize];
+                let slot_width = last_column.right() - first_column.x;
+                let slot_height = last_row.bottom() - first_row.y;
+                let (x, width) = self.justify_items.place(item.width, slot_width);
+                let (y, height) = self.align_items.place(item.height, slot_height);
+                Rect::new(first_column.x + x, first_row.y + y, width, height)
+            })
+            .collect()
+    }
+    //column, row and spans of every item, items with a position are placed first and
+    //the others fill the first free cells row by row
+    fn place(&self, items: &[GridItem]) -> Vec<(u16, u16, u16, u16)> {
+        let column_count = self.columns.len() as u16;
+        let mut occupied: Vec<Vec<bool>> = Vec::new();
+        let mut placements = vec![None; items.len()];
+        let fits = |occupied: &Vec<Vec<bool>>, column: u16, row: u16, w: u16, h: u16| {
+            column + w <= column_count
+                && (row..row + h).all(|r| {
+                    !occupied
+                        .get(r as usize)
+                        .is_some_and(|cells| (column..column + w).any(|c| cells[c as usize]))
+                })
+        };
+        let mark = |occupied: &mut Vec<Vec<bool>>, column: u16, row: u16, w: u16, h: u16| {
+            for r in row..row + h {
+                if occupied.len() <= r as usize {
+                    occupied.resize(r as usize + 1, vec![false; column_count as usize]);
+                }
+                for c in column..column + w {
+                    occupied[r as usize][c as usize] = true;
+                }
+            }
+        };
+        let explicit = items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| item.column.is_some() && item.row.is_some());
+        for (i, item) in explicit {
+            let column = item.column.unwrap_or(0).min(column_count - 1);
+            let row = item.row.unwrap_or(0);
+            let span = item.column_span.clamp(1, column_count - column);
+            mark(&mut occupied, column, row, span, item.row_span.max(1));
+            placements[i] = Some((column, row, span, item.row_span.max(1)));
+        }
+        for (i, item) in items.iter().enumerate() {
+            if placements[i].is_some() {
+                continue;
+            }
+            let span = item.column_span.clamp(1, column_count);
+            let row_span = item.row_span.max(1);
+            let mut row = item.row.unwrap_or(0);
+            let position = loop {
+                let found = match item.column {
+                    Some(column) => {
+                        let column = column.min(column_count - span);
+                        fits(&occupied, column, row, span, row_span).then_some(column)
+                    }
+                    None => {
+                        (0..=column_count - span).find(|&c| fits(&occupied, c, row, span, row_span))
+                    }
+                };
+                match found {
+                    Some(column) => break (column, row),
+                    //a fixed row that is full pushes the item to the next rows
+                    None => row += 1,
+                }
+            };
+            mark(&mut occupied, position.0, position.1, span, row_span);
+            placements[i] = Some((position.0, position.1, span, row_span));
+        }
+        placements
+            .into_iter()
+            .map(|p| p.expect("every item is placed"))
+            .collect()
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Constraint::*;
+
+    #[test]
+    fn test_cells() {
+        let grid = Grid::new([Length(3), Fill(1)], [Length(1), Length(2)]).gap(1);
+        assert_eq!(
+            grid.cells(Rect::new(0, 0, 10, 5)),
+            vec![
+                Rect::new(0, 0, 3, 1),
+                Rect::new(4, 0, 6, 1),
+                Rect::new(0, 2, 3, 2),
+                Rect::new(4, 2, 6, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_auto_placement_with_spans() {
+        let grid = Grid::new([Length(2); 3], [Length(1); 3]);
+        let items = [
+            GridItem::new().span(2, 1),
+            GridItem::new().span(2, 1),
+            GridItem::new(),
+            GridItem::new().at(2, 2),
+        ];
+        assert_eq!(
+            grid.split(Rect::new(0, 0, 6, 3), &items),
+            vec![
+                Rect::new(0, 0, 4, 1),
+                Rect::new(0, 1, 4, 1),
+                Rect::new(4, 0, 2, 1),
+                Rect::new(4, 2, 2, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_implicit_rows() {
+        let grid = Grid::new([Fill(1); 2], [Length(2)]).auto_row(Length(1));
+        let items = vec![GridItem::new(); 4];
+        let rects = grid.split(Rect::new(0, 0, 4, 10), &items);
+        assert_eq!(rects[2], Rect::new(0, 2, 2, 1));
+        assert_eq!(rects[3], Rect::new(2, 2, 2, 1));
+    }
+
+    #[test]
+    fn test_item_alignment() {
+        let grid = Grid::new([Length(6)], [Length(3)])
+            .justify_items(Align::Center)
+            .align_items(Align::End);
+        let rects = grid.split(Rect::new(1, 1, 6, 3), &[GridItem::new().size(2, 1)]);
+        assert_eq!(rects, vec![Rect::new(3, 3, 2, 1)]);
+    }
+}
@@ -0,0 +1,327 @@
+use super::rect::Rect;
+pub mod flex;
+pub mod grid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Constraint {
+    //exact number of cells
+    Length(u16),
+    //percentage of the space left after spacing
+    Percentage(u16),
+    //numerator and denominator of the space left after spacing
+    Ratio(u32, u32),
+    //at least this many cells, grows like Fill(1)
+    Min(u16),
+    //at most this many cells, grows like Fill(1)
+    Max(u16),
+    //share of the remaining space proportional to the weight
+    Fill(u16),
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    Horizontal,
+    Vertical,
+}
+//placement of an item on the cross axis or inside its cell
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Align {
+    Start,
+    Center,
+    End,
+    #[default]
+    Stretch,
+}
+impl Align {
+    //offset and size of an item inside a slot, items without a size fill the slot
+    //and a given size is never stretched
+    pub fn place(self, size: Option<u16>, slot: u16) -> (u16, u16) {
+        let Some(size) = size.map(|size| size.min(slot)) else {
+            return (0, slot);
+        };
+        match self {
+            Align::Start | Align::Stretch => (0, size),
+            Align::Center => ((slot - size) / 2, size),
+            Align::End => (slot - size, size),
+        }
+    }
+}
+//distribution of the free space between items on the main axis
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Justify {
+    #[default]
+    Start,
+    Center,
+    End,
+    SpaceBetween,
+    SpaceAround,
+    SpaceEvenly,
+}
+impl Justify {
+    //space before the first item and space after each item
+    pub(crate) fn distribute(self, free: u16, items: usize) -> (u16, Vec<u16>) {
+        match self {
+            _ if items == 0 => (0, Vec::new()),
+            Justify::Start => (0, vec![0; items]),
+            Justify::Center => (free / 2, vec![0; items]),
+            Justify::End => (free, vec![0; items]),
+            Justify::SpaceBetween => {
+                let mut after = split_evenly(free, items - 1);
+                after.push(0);
+                (0, after)
+            }
+            //every item gets the same space split between its two sides
+            Justify::SpaceAround => {
+                let around = split_evenly(free, items);
+                let after = (0..items)
+                    .map(|i| around[i] - around[i] / 2 + around.get(i + 1).map_or(0, |a| a / 2))
+                    .collect();
+                (around[0] / 2, after)
+            }
+            Justify::SpaceEvenly => {
+                let spaces = split_evenly(free, items + 1);
+                (spaces[0], spaces[1..].to_vec())
+            }
+        }
+    }
+}
+//total split in parts as equal as possible, the first parts get the leftover
+fn split_evenly(total: u16, parts: usize) -> Vec<u16> {
+    if parts == 0 {
+        return Vec::new();
+    }
+    let base = total / parts as u16;
+    let extra = (total % parts as u16) as usize;
+    (0..parts).map(|i| base + u16::from(i < extra)).collect()
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Padding {
+    pub top: u16,
+    pub right: u16,
+    pub bottom: u16,
+    pub left: u16,
+}
+impl Padding {
+    pub const fn new(top: u16, right: u16, bottom: u16, left: u16) -> Self {
+        Self {
+            top,
+            right,
+            bottom,
+            left,
+        }
+    }
+    pub const fn uniform(size: u16) -> Self {
+        Self::new(size, size, size, size)
+    }
+    pub const fn symmetric(horizontal: u16, vertical: u16) -> Self {
+        Self::new(vertical, horizontal, vertical, horizontal)
+    }
+    pub const fn horizontal(size: u16) -> Self {
+        Self::symmetric(size, 0)
+    }
+    pub const fn vertical(size: u16) -> Self {
+        Self::symmetric(0, size)
+    }
+}
+impl From<u16> for Padding {
+    fn from(size: u16) -> Self {
+        Self::uniform(size)
+    }
+}
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Layout {
+    pub orientation: Orientation,
+    pub constraints: Vec<Constraint>,
+    //space removed around the whole area before splitting
+    pub margin: Padding,
+    //cells left empty between two children
+    pub spacing: u16,
+    //space removed inside every child after splitting
+    pub padding: Padding,
+}
+impl Layout {
+    pub fn new(orientation: Orientation, constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self {
+            orientation,
+            constraints: constraints.into(),
+            ..Default::default()
+        }
+    }
+    pub fn horizontal(constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self::new(Orientation::Horizontal, constraints)
+    }
+    pub fn vertical(constraints: impl Into<Vec<Constraint>>) -> Self {
+        Self::new(Orientation::Vertical, constraints)
+    }
+    pub fn margin(mut self, margin: impl Into<Padding>) -> Self {
+        self.margin = margin.into();
+        self
+    }
+    pub fn spacing(mut self, spacing: u16) -> Self {
+        self.spacing = spacing;
+        self
+    }
+    pub fn padding(mut self, padding: impl Into<Padding>) -> Self {
+        self.padding = padding.into();
+        self
+    }
+    //one rect per constraint, in order
+    pub fn split(&self, area: Rect) -> Vec<Rect> {
+        let area = area.inner(self.margin);
+        let (start, lenght) = match self.orientation {
+            Orientation::Horizontal => (area.x, area.width),
+            Orientation::Vertical => (area.y, area.height),
+        };
+        let gaps = self.spacing as u32 * self.constraints.len().saturating_sub(1) as u32;
+        let available = (lenght as u32).saturating_sub(gaps) as u16;
+        let sizes = solve(&self.constraints, available);
+        let mut position = start;
+        sizes
+            .into_iter()
+            .map(|size| {
+                let rect = match self.orientation {
+                    Orientation::Horizontal => Rect::new(position, area.y, size, area.height),
+                    Orientation::Vertical => Rect::new(area.x, position, area.width, size),
+                };
+                position = position.saturating_add(size).saturating_add(self.spacing);
+                rect.inner(self.padding)
+            })
+            .collect()
+    }
+}
+//sizes of the constraints along the split axis, never summing over the available space
+pub(crate) fn solve(constraints: &[Constraint], available: u16) -> Vec<u16> {
+    let available = available as u32;
+    let mut sizes = vec![0u32; constraints.len()];
+    let mut used = 0u32;
+    //fixed sizes and minimums are served first, in order, until the space runs out
+    for (size, constraint) in sizes.iter_mut().zip(constraints) {
+        let wanted = match *constraint {
+            Constraint::Length(lenght) => lenght as u32,
+            Constraint::Percentage(percentage) => available * percentage.min(100) as u32 / 100,
+            Constraint::Ratio(_, 0) => 0,
+            Constraint::Ratio(numerator, denominator) => {
+                (available as u64 * numerator.min(denominator) as u64 / denominator as u64) as u32
+            }
+            Constraint::Min(min) => min as u32,
+            Constraint::Max(_) | Constraint::Fill(_) => 0,
+        };
+        *size = wanted.min(available - used);
+        used += *size;
+    }
+    //what is left goes to the growing constraints by weight, maximums that are reached
+    //drop out and their share is handed to the others
+    let mut growing: Vec<(usize, u32)> = constraints
+        .iter()
+        .enumerate()
+        .filter_map(|(i, constraint)| match *constraint {
+            Constraint::Min(_) | Constraint::Max(_) => Some((i, 1)),
+            Constraint::Fill(weight) => Some((i, weight as u32)),
+            _ => None,
+        })
+        .collect();
+    loop {
+        let remaining = available - used;
+        let total_weight: u32 = growing.iter().map(|(_, weight)| weight).sum();
+        if remaining == 0 || total_weight == 0 {
+            break;
+        }
+        let capped = growing
+            .iter()
+            .position(|&(i, weight)| match constraints[i] {
+                Constraint::Max(max) => remaining * weight / total_weight >= max as u32,
+                _ => false,
+            });
+        if let Some(position) = capped {
+            let (i, _) = growing.remove(position);
+            if let Constraint::Max(max) = constraints[i] {
+                sizes[i] = max as u32;
+                used += max as u32;
+            }
+            continue;
+        }
+        let mut given = 0;
+        for &(i, weight) in growing.iter() {
+            let share = remaining * weight / total_weight;
+            sizes[i] += share;
+            given += share;
+        }
+        //rounding leftovers go to the first growing constraints
+        for &(i, _) in growing.iter().filter(|(_, weight)| *weight > 0) {
+            if given == remaining {
+                break;
+            }
+            sizes[i] += 1;
+            given += 1;
+        }
+        break;
+    }
+    sizes.into_iter().map(|size| size as u16).collect()
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Constraint::*;
+
+    fn widths(layout: &Layout, width: u16) -> Vec<u16> {
+        layout
+            .split(Rect::new(0, 0, width, 1))
+            .iter()
+            .map(|r| r.width)
+            .collect()
+    }
+
+    #[test]
+    fn test_fixed() {
+        let layout = Layout::horizontal([Length(3), Percentage(50), Ratio(1, 4)]);
+        assert_eq!(widths(&layout, 20), vec![3, 10, 5]);
+    }
+
+    #[test]
+    fn test_fill() {
+        let layout = Layout::horizontal([Length(2), Fill(1), Fill(2)]);
+        assert_eq!(widths(&layout, 11), vec![2, 3, 6]);
+        let layout = Layout::horizontal([Fill(1), Fill(1), Fill(1)]);
+        assert_eq!(widths(&layout, 10), vec![4, 3, 3]);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let layout = Layout::horizontal([Min(5), Max(3), Fill(1)]);
+        assert_eq!(widths(&layout, 20), vec![11, 3, 6]);
+        let layout = Layout::horizontal([Min(5), Max(3)]);
+        assert_eq!(widths(&layout, 4), vec![4, 0]);
+    }
+
+    #[test]
+    fn test_overflow_is_clipped() {
+        let layout = Layout::horizontal([Length(6), Length(6), Fill(1)]);
+        assert_eq!(widths(&layout, 8), vec![6, 2, 0]);
+    }
+
+    #[test]
+    fn test_spacing_and_margin() {
+        let layout = Layout::vertical([Length(2), Fill(1)]).margin(1).spacing(1);
+        assert_eq!(
+            layout.split(Rect::new(0, 0, 10, 10)),
+            vec![Rect::new(1, 1, 8, 2), Rect::new(1, 4, 8, 5)]
+        );
+    }
+
+    #[test]
+    fn test_padding() {
+        let layout = Layout::horizontal([Fill(1), Fill(1)]).padding(Padding::horizontal(1));
+        assert_eq!(
+            layout.split(Rect::new(2, 0, 10, 3)),
+            vec![Rect::new(3, 0, 3, 3), Rect::new(8, 0, 3, 3)]
+        );
+    }
+
+    #[test]
+    fn test_adapts_to_resize() {
+        let layout = Layout::horizontal([Length(4), Fill(1)]);
+        assert_eq!(widths(&layout, 10), vec![4, 6]);
+        assert_eq!(widths(&layout, 30), vec![4, 26]);
+        assert_eq!(widths(&layout, 2), vec![2, 0]);
+    }
+}
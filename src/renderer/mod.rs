@@ -1,12 +1,20 @@
 pub mod buffer;
 pub mod buffer_mediator;
 pub mod core_widgetes;
+pub mod hit_test;
+pub mod input;
+pub mod layout;
 pub mod painter;
 pub mod rect;
+pub mod snapshot;
 pub mod terminal_writer;
+pub mod test_backend;
+pub mod text;
 pub mod widget;
 use compact_str::{CompactString, ToCompactString};
-use crossterm::style::Color;
+use crossterm::style::{Attribute, Attributes, Color};
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
 #[derive(Clone, Copy)]
 pub enum Direction {
     UP,
@@ -14,14 +22,14 @@ pub enum Direction {
     Left,
     Right,
 }
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum BoxSide {
     UP,
     Down,
     Left,
     Right,
 }
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct BackgroundColor(Color);
 
 impl Default for BackgroundColor {
@@ -29,20 +37,101 @@ impl Default for BackgroundColor {
         Self(Color::Black)
     }
 }
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct ForegroundColor(Color);
 impl Default for ForegroundColor {
     fn default() -> Self {
         Self(Color::White)
     }
 }
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub struct Simble(CompactString);
 impl Default for Simble {
     fn default() -> Self {
         Simble(CompactString::const_new(" "))
     }
 }
+impl Simble {
+    //placeholder for the cell covered by the right half of a wide simble
+    pub const fn continuation() -> Simble {
+        Simble(CompactString::const_new(""))
+    }
+    //a simble known at compile time, for symbol sets kept in consts
+    pub const fn from_static(text: &'static str) -> Simble {
+        Simble(CompactString::const_new(text))
+    }
+    pub fn is_continuation(&self) -> bool {
+        self.0.is_empty()
+    }
+    pub fn as_str(&self) -> &str {
+        self.0.as_str()
+    }
+    //number of columns the simble takes on screen
+    pub fn width(&self) -> u16 {
+        self.0.width().min(2) as u16
+    }
+    //one simble per grapheme cluster, wide graphemes are followed by a continuation
+    //and graphemes with no width like control characters are dropped
+    pub fn from_text(text: &str) -> Vec<Simble> {
+        let mut simbles = Vec::new();
+        for grapheme in text.graphemes(true) {
+            if grapheme.chars().all(char::is_control) {
+                continue;
+            }
+            let simble = Simble(grapheme.to_compact_string());
+            match simble.width() {
+                0 => {}
+                1 => simbles.push(simble),
+                _ => {
+                    simbles.push(simble);
+                    simbles.push(Simble::continuation());
+                }
+            }
+        }
+        simbles
+    }
+}
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct TextAttributes(Attributes);
+impl TextAttributes {
+    //attributes that can be set and unset by the terminal writer
+    pub const SUPPORTED: [Attribute; 9] = [
+        Attribute::Bold,
+        Attribute::Dim,
+        Attribute::Italic,
+        Attribute::Underlined,
+        Attribute::SlowBlink,
+        Attribute::RapidBlink,
+        Attribute::Reverse,
+        Attribute::Hidden,
+        Attribute::CrossedOut,
+    ];
+    pub const fn none() -> Self {
+        Self(Attributes::none())
+    }
+    pub const fn with(self, attribute: Attribute) -> Self {
+        Self(self.0.with(attribute))
+    }
+    pub const fn without(self, attribute: Attribute) -> Self {
+        Self(self.0.without(attribute))
+    }
+    pub const fn has(self, attribute: Attribute) -> bool {
+        self.0.has(attribute)
+    }
+    pub const fn is_empty(self) -> bool {
+        self.0.is_empty()
+    }
+}
+impl From<Attribute> for TextAttributes {
+    fn from(attribute: Attribute) -> Self {
+        Self(attribute.into())
+    }
+}
+impl From<Attributes> for TextAttributes {
+    fn from(attributes: Attributes) -> Self {
+        Self(attributes)
+    }
+}
 
 impl Into<ForegroundColor> for Color {
     fn into(self) -> ForegroundColor {
@@ -64,3 +153,24 @@ impl Into<Simble> for char {
         Simble(self.to_compact_string())
     }
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_text() {
+        let simbles = Simble::from_text("a中e\u{301}\t");
+        let text: Vec<&str> = simbles.iter().map(|s| s.as_str()).collect();
+        assert_eq!(text, vec!["a", "中", "", "e\u{301}"]);
+        assert!(simbles[2].is_continuation());
+    }
+
+    #[test]
+    fn test_width() {
+        let narrow: Simble = 'a'.into();
+        let wide: Simble = '中'.into();
+        assert_eq!(narrow.width(), 1);
+        assert_eq!(wide.width(), 2);
+        assert_eq!(Simble::continuation().width(), 0);
+    }
+}
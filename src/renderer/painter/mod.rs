@@ -1,15 +1,23 @@
-use compact_str::ToCompactString;
+pub mod paragraph;
 
 //use super::buffer_mediator::BufferMediator;
-use super::{rect::Rect, widget::Widget, BackgroundColor, ForegroundColor, Simble};
+use super::text::{Style, Text};
+use super::{
+    rect::Rect,
+    widget::{StatefulWidget, Widget},
+    BackgroundColor, ForegroundColor, Simble, TextAttributes,
+};
+use paragraph::{LayoutLine, ParagraphLayout, Wrap};
 
 pub trait Painter: Sized + Sync + Send {
     fn background_fill(&mut self, color: BackgroundColor, area: Option<Rect>);
     fn foreground_fill(&mut self, color: ForegroundColor, area: Option<Rect>);
     fn simble_fill(&mut self, color: Simble, area: Option<Rect>);
+    fn attributes_fill(&mut self, attributes: TextAttributes, area: Option<Rect>);
     fn write_simbles(&mut self, text: &[Simble], area: Rect);
     fn write_background_color(&mut self, color: &[BackgroundColor], area: Rect);
     fn write_foreground_color(&mut self, color: &[ForegroundColor], area: Rect);
+    fn write_attributes(&mut self, attributes: &[TextAttributes], area: Rect);
     fn area(&self) -> Rect;
     fn render_widget(
         &mut self,
@@ -18,55 +26,167 @@ pub trait Painter: Sized + Sync + Send {
         scroll_x: u16,
         scroll_y: u16,
     );
+    fn render_stateful_widget<T: StatefulWidget<Self> + ?Sized>(
+        &mut self,
+        widget: &T,
+        state: &mut T::State,
+        area: Rect,
+        scroll_x: u16,
+        scroll_y: u16,
+    );
 }
 pub trait TextPainer {
     fn write_text_line(&mut self, text: &str, x: u16, y: u16);
     fn write_paragraph(&mut self, text: &str, x: u16, y: u16, line_break: Option<u16>);
+    //lays the text out inside the area and returns the number of lines written
+    fn write_paragraph_layout(&mut self, text: &str, area: Rect, layout: &ParagraphLayout) -> u16;
+    //lays the styled text out inside the area writing simbles, colors and attributes together
+    fn write_text(&mut self, text: &Text, area: Rect, layout: &ParagraphLayout) -> u16;
+    fn write_layout_lines(&mut self, lines: &[LayoutLine], x: u16, y: u16);
 }
 impl<T: Painter> TextPainer for T {
     fn write_text_line(&mut self, text: &str, x: u16, y: u16) {
-        let simblevector: Vec<Simble> = text
-            .chars()
-            .map(|c| Simble(c.to_compact_string()))
-            .collect();
+        let simblevector = Simble::from_text(text);
         self.write_simbles(
             simblevector.as_slice(),
             Rect::new(x, y, simblevector.len() as u16, 1),
         );
     }
     fn write_paragraph(&mut self, text: &str, x: u16, y: u16, line_break: Option<u16>) {
-        let mut curret_y = y;
-        match line_break {
-            Some(breaking_point) => {
-                let mut simbles = Vec::new();
-                let mut line_lenght = 0;
-                for c in text.chars() {
-                    if c == 0xA as char || line_lenght > breaking_point {
-                        self.write_simbles(
-                            simbles.as_slice(),
-                            Rect::new(x, curret_y, simbles.len() as u16, 1),
-                        );
-                        simbles = Vec::new();
-                        curret_y += 1;
-                        line_lenght = 0;
-                    }
-                    if c != 0xA as char {
-                        simbles.push(Simble(c.to_compact_string()));
-                        line_lenght += 1;
-                    }
-                }
-                if !simbles.is_empty() {
-                    self.write_simbles(
-                        simbles.as_slice(),
-                        Rect::new(x, curret_y, simbles.len() as u16, 1),
-                    );
-                }
+        let layout = match line_break {
+            Some(_) => ParagraphLayout::new(),
+            None => ParagraphLayout::new().wrap(Wrap::None),
+        };
+        let width = line_break.unwrap_or(u16::MAX - x);
+        self.write_layout_lines(&layout.layout(text, width), x, y);
+    }
+    fn write_paragraph_layout(&mut self, text: &str, area: Rect, layout: &ParagraphLayout) -> u16 {
+        self.write_text(&Text::from(text), area, layout)
+    }
+    fn write_text(&mut self, text: &Text, area: Rect, layout: &ParagraphLayout) -> u16 {
+        //the area height acts as a max height so the overflow indicator shows when text is cut
+        let max_height = layout.max_height.unwrap_or(area.height).min(area.height);
+        let layout = ParagraphLayout {
+            max_height: Some(max_height),
+            ..layout.clone()
+        };
+        let lines = layout.layout_text(text, area.width);
+        self.write_layout_lines(&lines, area.x, area.y);
+        lines.len() as u16
+    }
+    fn write_layout_lines(&mut self, lines: &[LayoutLine], x: u16, y: u16) {
+        for (i, line) in lines.iter().enumerate() {
+            let x = x.saturating_add(line.offset);
+            let y = y.saturating_add(i as u16);
+            self.write_simbles(
+                line.simbles.as_slice(),
+                Rect::new(x, y, line.simbles.len() as u16, 1),
+            );
+            let run_area =
+                |start: u16, len: usize| Rect::new(x.saturating_add(start), y, len as u16, 1);
+            //colors and attributes are written per run so unstyled text keeps what is under it
+            for (start, run) in style_runs(&line.styles, |s| s.foreground) {
+                self.write_foreground_color(&run, run_area(start, run.len()));
+            }
+            for (start, run) in style_runs(&line.styles, |s| s.background) {
+                self.write_background_color(&run, run_area(start, run.len()));
             }
-            None => {
-                for line in text.lines() {
-                    self.write_text_line(line, x, curret_y);
-                }
+            for (start, run) in style_runs(&line.styles, |s| s.attributes) {
+                self.write_attributes(&run, run_area(start, run.len()));
             }
         }
     }
 }
+//contiguous runs of cells where the style sets the value, with the column they start at
+fn style_runs<T>(styles: &[Style], value: impl Fn(&Style) -> Option<T>) -> Vec<(u16, Vec<T>)> {
+    let mut runs: Vec<(u16, Vec<T>)> = Vec::new();
+    let mut last = None;
+    for (i, style) in styles.iter().enumerate() {
+        match value(style) {
+            Some(v) if last == Some(i) => {
+                runs.last_mut().expect("a run is open").1.push(v);
+                last = Some(i + 1);
+            }
+            Some(v) => {
+                runs.push((i as u16, vec![v]));
+                last = Some(i + 1);
+            }
+            None => {}
+        }
+    }
+    runs
+}
+#[cfg(test)]
+mod tests {
+    use super::{
+        paragraph::{Alignment, ParagraphLayout},
+        Painter, TextPainer,
+    };
+    use crate::renderer::{
+        rect::Rect,
+        test_backend::TB,
+        text::{Line, Span, Style, Text},
+        ForegroundColor,
+    };
+    use crossterm::style::{Attribute, Color};
+
+    #[test]
+    fn test_write_paragraph_breaks_on_words() {
+        let mut backend = TB::new(8, 3);
+        backend.write_paragraph("one two three", 1, 0, Some(7));
+        backend.assert_text(&[" one two", " three  ", "        "]);
+    }
+
+    #[test]
+    fn test_write_paragraph_lines() {
+        let mut backend = TB::new(4, 3);
+        backend.write_paragraph("ab\ncd", 1, 1, None);
+        backend.assert_text(&["    ", " ab ", " cd "]);
+    }
+
+    #[test]
+    fn test_write_paragraph_layout() {
+        let mut backend = TB::new(8, 3);
+        let layout = ParagraphLayout::new()
+            .alignment(Alignment::Right)
+            .overflow('…');
+        let written =
+            backend.write_paragraph_layout("aa bb cc dd ee", Rect::new(1, 1, 6, 2), &layout);
+        assert_eq!(written, 2);
+        backend.assert_text(&["        ", "  aa bb ", " cc dd… "]);
+    }
+
+    #[test]
+    fn test_write_text_styles() {
+        let mut backend = TB::new(8, 2);
+        backend.background_fill(Color::Blue.into(), None);
+        let key = Style::new()
+            .fg(Color::Yellow)
+            .add_attribute(Attribute::Bold);
+        let text = Text::from(Line::from(vec![
+            Span::styled("[q]", key),
+            Span::raw(" quit"),
+        ]));
+        backend.write_text(&text, Rect::new(0, 0, 8, 2), &ParagraphLayout::new());
+        backend.assert_text(&["[q] quit", "        "]);
+        let cell = backend.cell(1, 0);
+        assert_eq!(cell.foreground, Color::Yellow.into());
+        assert!(cell.attributes.has(Attribute::Bold));
+        assert_eq!(cell.background, Color::Blue.into());
+        let cell = backend.cell(4, 0);
+        assert_eq!(cell.foreground, ForegroundColor::default());
+        assert!(cell.attributes.is_empty());
+    }
+
+    #[test]
+    fn test_write_styled_text_far_right() {
+        let mut backend = TB::new(4, 1);
+        let text = Text::from(Line::from(vec![
+            Span::raw("ab"),
+            Span::styled("cd", Style::new().fg(Color::Red)),
+        ]));
+        let area = Rect::new(u16::MAX - 1, 0, 4, 1);
+        backend.write_text(&text, area, &ParagraphLayout::new());
+        backend.assert_text(&["    "]);
+    }
+}
@@ -0,0 +1,399 @@
+use crate::renderer::{
+    text::{Line, Style, Text},
+    Simble,
+};
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Wrap {
+    //break between words, words longer than a line are split
+    #[default]
+    Word,
+    //break as soon as the line is full
+    Hard,
+    //lines longer than the width are truncated
+    None,
+}
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    #[default]
+    Left,
+    Center,
+    Right,
+    //stretch the spaces between words so wrapped lines fill the width
+    Justify,
+}
+//a line ready to be written, offset is the column where it starts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LayoutLine {
+    pub offset: u16,
+    pub simbles: Vec<Simble>,
+    //style of each simble
+    pub styles: Vec<Style>,
+}
+//a simble with the style of the span it comes from
+type Glyph = (Simble, Style);
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParagraphLayout {
+    pub wrap: Wrap,
+    pub alignment: Alignment,
+    //replaces the end of lines truncated by Wrap::None
+    pub ellipsis: Option<Simble>,
+    pub tab_width: u16,
+    pub max_height: Option<u16>,
+    //replaces the end of the last line when lines are cut by the max height
+    pub overflow: Option<Simble>,
+}
+impl Default for ParagraphLayout {
+    fn default() -> Self {
+        Self {
+            wrap: Wrap::default(),
+            alignment: Alignment::default(),
+            ellipsis: None,
+            tab_width: 4,
+            max_height: None,
+            overflow: None,
+        }
+    }
+}
+impl ParagraphLayout {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+    pub fn alignment(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self
+    }
+    pub fn ellipsis(mut self, ellipsis: impl Into<Simble>) -> Self {
+        self.ellipsis = Some(ellipsis.into());
+        self
+    }
+    pub fn tab_width(mut self, tab_width: u16) -> Self {
+        self.tab_width = tab_width;
+        self
+    }
+    pub fn max_height(mut self, max_height: u16) -> Self {
+        self.max_height = Some(max_height);
+        self
+    }
+    pub fn overflow(mut self, overflow: impl Into<Simble>) -> Self {
+        self.overflow = Some(overflow.into());
+        self
+    }
+    pub fn layout(&self, text: &str, width: u16) -> Vec<LayoutLine> {
+        self.layout_text(&Text::from(text), width)
+    }
+    pub fn layout_text(&self, text: &Text, width: u16) -> Vec<LayoutLine> {
+        if width == 0 {
+            return Vec::new();
+        }
+        //lines paired with a flag telling if they end a paragraph, those are never justified
+        let mut lines: Vec<(Vec<Glyph>, bool)> = Vec::new();
+        for line in text.lines.iter() {
+            let glyphs = self.expand_tabs(line);
+            let mut wrapped = match self.wrap {
+                Wrap::Word => wrap_words(&glyphs, width),
+                Wrap::Hard => split_at_width(&glyphs, width),
+                Wrap::None => vec![truncate(&glyphs, width, self.ellipsis.as_ref())],
+            };
+            if wrapped.is_empty() {
+                wrapped.push(Vec::new());
+            }
+            let last = wrapped.len() - 1;
+            lines.extend(wrapped.into_iter().enumerate().map(|(i, l)| (l, i == last)));
+        }
+        if let Some(max_height) = self.max_height {
+            if lines.len() > max_height as usize {
+                lines.truncate(max_height as usize);
+                if let (Some(overflow), Some(last)) = (self.overflow.as_ref(), lines.last_mut()) {
+                    *last = (end_with(&last.0, width, overflow), true);
+                }
+            }
+        }
+        lines
+            .into_iter()
+            .map(|(glyphs, end)| self.align(glyphs, width, end))
+            .collect()
+    }
+    fn expand_tabs(&self, line: &Line) -> Vec<Glyph> {
+        let mut glyphs = Vec::new();
+        for span in line.spans.iter() {
+            for grapheme in span.content.graphemes(true) {
+                if grapheme == "\t" {
+                    let tab_width = self.tab_width.max(1) as usize;
+                    let spaces = tab_width - glyphs.len() % tab_width;
+                    glyphs.resize(glyphs.len() + spaces, (Simble::default(), span.style));
+                } else {
+                    glyphs.extend(
+                        Simble::from_text(grapheme)
+                            .into_iter()
+                            .map(|simble| (simble, span.style)),
+                    );
+                }
+            }
+        }
+        glyphs
+    }
+    fn align(&self, glyphs: Vec<Glyph>, width: u16, end: bool) -> LayoutLine {
+        let free = width.saturating_sub(glyphs.len() as u16);
+        let (offset, glyphs) = match self.alignment {
+            Alignment::Left => (0, glyphs),
+            Alignment::Center => (free / 2, glyphs),
+            Alignment::Right => (free, glyphs),
+            Alignment::Justify if end => (0, glyphs),
+            Alignment::Justify => (0, justify(glyphs, free)),
+        };
+        let (simbles, styles) = glyphs.into_iter().unzip();
+        LayoutLine {
+            offset,
+            simbles,
+            styles,
+        }
+    }
+}
+fn is_space(glyph: &Glyph) -> bool {
+    glyph.0.as_str() == " "
+}
+//groups a wide simble with its continuation so they are never split
+fn units(glyphs: &[Glyph]) -> Vec<&[Glyph]> {
+    let mut units = Vec::new();
+    let mut i = 0;
+    while i < glyphs.len() {
+        let lenght = if glyphs.get(i + 1).is_some_and(|g| g.0.is_continuation()) {
+            2
+        } else {
+            1
+        };
+        units.push(&glyphs[i..i + lenght]);
+        i += lenght;
+    }
+    units
+}
+fn split_at_width(glyphs: &[Glyph], width: u16) -> Vec<Vec<Glyph>> {
+    let mut lines = Vec::new();
+    let mut line: Vec<Glyph> = Vec::new();
+    for unit in units(glyphs) {
+        if line.len() + unit.len() > width as usize && !line.is_empty() {
+            lines.push(std::mem::take(&mut line));
+        }
+        line.extend_from_slice(unit);
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+fn take_width(glyphs: &[Glyph], width: u16) -> Vec<Glyph> {
+    let mut line = Vec::new();
+    for unit in units(glyphs) {
+        if line.len() + unit.len() > width as usize {
+            break;
+        }
+        line.extend_from_slice(unit);
+    }
+    line
+}
+//cuts the line so the marker fits at its end, the marker takes the style of the text before it
+fn end_with(glyphs: &[Glyph], width: u16, marker: &Simble) -> Vec<Glyph> {
+    let mut line = take_width(glyphs, width.saturating_sub(marker.width()));
+    let style = line.last().map(|g| g.1).unwrap_or_default();
+    line.push((marker.clone(), style));
+    if marker.width() > 1 {
+        line.push((Simble::continuation(), style));
+    }
+    line
+}
+fn truncate(glyphs: &[Glyph], width: u16, ellipsis: Option<&Simble>) -> Vec<Glyph> {
+    if glyphs.len() <= width as usize {
+        return glyphs.to_vec();
+    }
+    match ellipsis {
+        Some(ellipsis) => end_with(glyphs, width, ellipsis),
+        None => take_width(glyphs, width),
+    }
+}
+fn wrap_words(glyphs: &[Glyph], width: u16) -> Vec<Vec<Glyph>> {
+    let width = width as usize;
+    let mut lines = Vec::new();
+    let mut line: Vec<Glyph> = Vec::new();
+    let mut spaces: Vec<Glyph> = Vec::new();
+    //spaces at the start of the paragraph are kept as indentation
+    let mut first = true;
+    let mut i = 0;
+    while i < glyphs.len() {
+        let space = is_space(&glyphs[i]);
+        let start = i;
+        while i < glyphs.len() && is_space(&glyphs[i]) == space {
+            i += 1;
+        }
+        let token = &glyphs[start..i];
+        if space {
+            spaces = token.to_vec();
+            continue;
+        }
+        if line.len() + spaces.len() + token.len() <= width {
+            if !line.is_empty() || first {
+                line.append(&mut spaces);
+            }
+        } else {
+            if !line.is_empty() {
+                lines.push(std::mem::take(&mut line));
+            }
+            if token.len() > width {
+                let mut parts = split_at_width(token, width as u16);
+                line = parts.pop().unwrap_or_default();
+                lines.append(&mut parts);
+                spaces.clear();
+                first = false;
+                continue;
+            }
+        }
+        spaces.clear();
+        line.extend_from_slice(token);
+        first = false;
+    }
+    if !line.is_empty() {
+        lines.push(line);
+    }
+    lines
+}
+//spreads the free columns over the gaps between words, leftmost gaps get the extra ones
+//the added spaces take the style of the gap they widen
+fn justify(glyphs: Vec<Glyph>, free: u16) -> Vec<Glyph> {
+    let gaps = glyphs
+        .windows(2)
+        .filter(|w| !is_space(&w[0]) && is_space(&w[1]))
+        .count();
+    if gaps == 0 || free == 0 {
+        return glyphs;
+    }
+    let mut justified = Vec::with_capacity(glyphs.len() + free as usize);
+    let mut gap = 0;
+    for (i, glyph) in glyphs.iter().enumerate() {
+        justified.push(glyph.clone());
+        if let Some(next) = glyphs
+            .get(i + 1)
+            .filter(|next| !is_space(glyph) && is_space(next))
+        {
+            let extra = free as usize / gaps + usize::from(gap < free as usize % gaps);
+            justified.resize(justified.len() + extra, (Simble::default(), next.1));
+            gap += 1;
+        }
+    }
+    justified
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text_of(lines: &[LayoutLine]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|l| {
+                let simbles: String = l.simbles.iter().map(|s| s.as_str()).collect();
+                format!("{}{}", " ".repeat(l.offset as usize), simbles)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_word_wrap() {
+        let layout = ParagraphLayout::new();
+        let lines = layout.layout("the quick brown fox jumps", 10);
+        assert_eq!(text_of(&lines), vec!["the quick", "brown fox", "jumps"]);
+    }
+
+    #[test]
+    fn test_word_wrap_long_word() {
+        let layout = ParagraphLayout::new();
+        let lines = layout.layout("a abcdefghij b", 4);
+        assert_eq!(text_of(&lines), vec!["a", "abcd", "efgh", "ij b"]);
+    }
+
+    #[test]
+    fn test_word_wrap_keeps_indentation_and_empty_lines() {
+        let layout = ParagraphLayout::new();
+        let lines = layout.layout("  ab cd\n\nef", 5);
+        assert_eq!(text_of(&lines), vec!["  ab", "cd", "", "ef"]);
+    }
+
+    #[test]
+    fn test_hard_wrap() {
+        let layout = ParagraphLayout::new().wrap(Wrap::Hard);
+        let lines = layout.layout("abcdefg", 3);
+        assert_eq!(text_of(&lines), vec!["abc", "def", "g"]);
+    }
+
+    #[test]
+    fn test_hard_wrap_wide() {
+        let layout = ParagraphLayout::new().wrap(Wrap::Hard);
+        let lines = layout.layout("a中文", 4);
+        assert_eq!(text_of(&lines), vec!["a中", "文"]);
+        assert_eq!(lines[0].simbles.len(), 3);
+    }
+
+    #[test]
+    fn test_alignment() {
+        let center = ParagraphLayout::new().alignment(Alignment::Center);
+        assert_eq!(center.layout("ab", 7)[0].offset, 2);
+        let right = ParagraphLayout::new().alignment(Alignment::Right);
+        assert_eq!(right.layout("ab", 7)[0].offset, 5);
+    }
+
+    #[test]
+    fn test_justify() {
+        let layout = ParagraphLayout::new().alignment(Alignment::Justify);
+        let lines = layout.layout("a b c d e", 6);
+        assert_eq!(text_of(&lines), vec!["a  b c", "d e"]);
+    }
+
+    #[test]
+    fn test_truncate() {
+        let layout = ParagraphLayout::new().wrap(Wrap::None).ellipsis('…');
+        let lines = layout.layout("abcdefgh\nabc", 5);
+        assert_eq!(text_of(&lines), vec!["abcd…", "abc"]);
+        let layout = ParagraphLayout::new().wrap(Wrap::None);
+        assert_eq!(text_of(&layout.layout("abcdefgh", 5)), vec!["abcde"]);
+    }
+
+    #[test]
+    fn test_tabs() {
+        let layout = ParagraphLayout::new().tab_width(4).wrap(Wrap::None);
+        let lines = layout.layout("a\tb\t\tc", 20);
+        assert_eq!(text_of(&lines), vec!["a   b       c"]);
+    }
+
+    #[test]
+    fn test_max_height() {
+        let layout = ParagraphLayout::new().max_height(2).overflow('…');
+        let lines = layout.layout("one two three four", 5);
+        assert_eq!(text_of(&lines), vec!["one", "two…"]);
+        let layout = ParagraphLayout::new().max_height(2);
+        let lines = layout.layout("one two three four", 5);
+        assert_eq!(text_of(&lines), vec!["one", "two"]);
+    }
+
+    #[test]
+    fn test_styles_follow_spans() {
+        use crate::renderer::text::Span;
+        use crossterm::style::Color;
+        let key = Style::new().fg(Color::Yellow);
+        let text = Text::from(Line::from(vec![
+            Span::styled("[q]", key),
+            Span::raw(" quit"),
+        ]));
+        let lines = ParagraphLayout::new().layout_text(&text, 4);
+        assert_eq!(text_of(&lines), vec!["[q]", "quit"]);
+        assert_eq!(lines[0].styles, vec![key; 3]);
+        assert_eq!(lines[1].styles, vec![Style::default(); 4]);
+    }
+
+    #[test]
+    fn test_zero_width() {
+        assert!(ParagraphLayout::new().layout("abc", 0).is_empty());
+    }
+}
@@ -1,3 +1,4 @@
+use super::layout::Padding;
 use std::cmp::{max, min};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -22,6 +23,9 @@ impl Rect {
             && (self.x + self.width) >= (other.x + other.width)
             && (self.y + self.height) >= (other.y + other.height);
     }
+    pub fn contains_point(&self, x: u16, y: u16) -> bool {
+        x >= self.x && y >= self.y && x < self.right() && y < self.bottom()
+    }
     pub fn offset(&self, x: u16, y: u16) -> Rect {
         Rect::new(self.x + x, self.y + y, self.width, self.height)
     }
@@ -37,11 +41,26 @@ impl Rect {
             height: y2.saturating_sub(y1),
         }
     }
+    //the rect shrunk by the padding on every side
+    pub fn inner(&self, padding: Padding) -> Rect {
+        let width = self
+            .width
+            .saturating_sub(padding.left.saturating_add(padding.right));
+        let height = self
+            .height
+            .saturating_sub(padding.top.saturating_add(padding.bottom));
+        Rect::new(
+            self.x + padding.left.min(self.width),
+            self.y + padding.top.min(self.height),
+            width,
+            height,
+        )
+    }
     pub fn bottom(&self) -> u16 {
-        self.height + self.y
+        self.y.saturating_add(self.height)
     }
     pub fn right(&self) -> u16 {
-        self.width + self.x
+        self.x.saturating_add(self.width)
     }
     pub fn left(&self) -> u16 {
         self.x
@@ -77,6 +96,15 @@ mod tests {
         assert!(!rect1.contains(&rect3));
     }
 
+    #[test]
+    fn test_contains_point() {
+        let rect = Rect::new(2, 3, 4, 2);
+        assert!(rect.contains_point(2, 3));
+        assert!(rect.contains_point(5, 4));
+        assert!(!rect.contains_point(6, 4));
+        assert!(!rect.contains_point(2, 5));
+    }
+
     #[test]
     fn test_offset() {
         let rect = Rect::new(10, 20, 30, 40);
@@ -131,4 +159,17 @@ mod tests {
         let rect = Rect::new(10, 20, 30, 40);
         assert_eq!(rect.top(), 20);
     }
+
+    #[test]
+    fn test_inner() {
+        let rect = Rect::new(10, 20, 30, 40);
+        assert_eq!(
+            rect.inner(Padding::new(1, 2, 3, 4)),
+            Rect::new(14, 21, 24, 36)
+        );
+        assert_eq!(
+            Rect::new(0, 0, 2, 2).inner(Padding::uniform(3)),
+            Rect::new(2, 2, 0, 0)
+        );
+    }
 }
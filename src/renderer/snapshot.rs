@@ -0,0 +1,431 @@
+use super::{
+    buffer::Buffer, test_backend::TestBackend, BackgroundColor, ForegroundColor, Simble,
+    TextAttributes,
+};
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    path::{Path, PathBuf},
+};
+
+//set to 1 to overwrite the stored snapshots with the current output
+pub const BLESS_VAR: &str = "SMALLTUI_BLESS";
+const PALETTE_KEYS: &str = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+//a rendered frame in a form that can be stored as text and compared cell by cell
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Snapshot {
+    width: u16,
+    height: u16,
+    simbles: Vec<Vec<String>>,
+    foreground: Vec<Vec<String>>,
+    background: Vec<Vec<String>>,
+    attributes: Vec<Vec<String>>,
+}
+impl Snapshot {
+    pub fn from_backend<A, B, C, D>(backend: &TestBackend<A, B, C, D>) -> Snapshot
+    where
+        A: Buffer<BackgroundColor>,
+        B: Buffer<ForegroundColor>,
+        C: Buffer<Simble>,
+        D: Buffer<TextAttributes>,
+    {
+        let area = backend.frame_area();
+        let mut simbles = Vec::new();
+        let mut foreground = Vec::new();
+        let mut background = Vec::new();
+        let mut attributes = Vec::new();
+        for y in 0..area.height {
+            let cells: Vec<_> = (0..area.width).map(|x| backend.cell(x, y)).collect();
+            simbles.push(cells.iter().map(|c| c.simble.0.to_string()).collect());
+            foreground.push(
+                cells
+                    .iter()
+                    .map(|c| format!("{:?}", c.foreground.0))
+                    .collect(),
+            );
+            background.push(
+                cells
+                    .iter()
+                    .map(|c| format!("{:?}", c.background.0))
+                    .collect(),
+            );
+            attributes.push(
+                cells
+                    .iter()
+                    .map(|c| attributes_name(c.attributes))
+                    .collect(),
+            );
+        }
+        Snapshot {
+            width: area.width,
+            height: area.height,
+            simbles,
+            foreground,
+            background,
+            attributes,
+        }
+    }
+    //colors and attributes are stored as one palette key per cell, the palette maps keys to names
+    pub fn serialize(&self) -> String {
+        let mut palette: Vec<&str> = Vec::new();
+        let styles = self
+            .foreground
+            .iter()
+            .chain(self.background.iter())
+            .chain(self.attributes.iter());
+        for name in styles.flatten() {
+            if !palette.contains(&name.as_str()) {
+                palette.push(name);
+            }
+        }
+        if palette.len() > PALETTE_KEYS.len() {
+            panic!("snapshots support at most {} styles", PALETTE_KEYS.len());
+        }
+        let key = |name: &String| {
+            let index = palette.iter().position(|p| p == name).unwrap();
+            PALETTE_KEYS.as_bytes()[index] as char
+        };
+        let mut out = String::new();
+        writeln!(out, "size {}x{}", self.width, self.height).unwrap();
+        writeln!(out, "simbles").unwrap();
+        for row in self.simbles.iter() {
+            writeln!(out, "|{}|", row.concat()).unwrap();
+        }
+        writeln!(out, "foreground").unwrap();
+        for row in self.foreground.iter() {
+            writeln!(out, "|{}|", row.iter().map(key).collect::<String>()).unwrap();
+        }
+        writeln!(out, "background").unwrap();
+        for row in self.background.iter() {
+            writeln!(out, "|{}|", row.iter().map(key).collect::<String>()).unwrap();
+        }
+        writeln!(out, "attributes").unwrap();
+        for row in self.attributes.iter() {
+            writeln!(out, "|{}|", row.iter().map(key).collect::<String>()).unwrap();
+        }
+        writeln!(out, "palette").unwrap();
+        for (i, name) in palette.iter().enumerate() {
+            writeln!(out, "{} {}", PALETTE_KEYS.as_bytes()[i] as char, name).unwrap();
+        }
+        out
+    }
+    pub fn parse(input: &str) -> Result<Snapshot, String> {
+        let mut lines = input.lines();
+        let size = lines
+            .next()
+            .and_then(|l| l.strip_prefix("size "))
+            .ok_or("missing size header")?;
+        let (width, height) = size.split_once('x').ok_or("malformed size header")?;
+        let width: u16 = width.parse().map_err(|_| "malformed width")?;
+        let height: u16 = height.parse().map_err(|_| "malformed height")?;
+        let mut grid = |section: &str| -> Result<Vec<String>, String> {
+            if lines.next() != Some(section) {
+                return Err(format!("missing {} section", section));
+            }
+            (0..height)
+                .map(|_| {
+                    lines
+                        .next()
+                        .and_then(|l| l.strip_prefix('|'))
+                        .and_then(|l| l.strip_suffix('|'))
+                        .map(str::to_string)
+                        .ok_or(format!("malformed {} row", section))
+                })
+                .collect()
+        };
+        let simble_rows = grid("simbles")?;
+        let foreground_rows = grid("foreground")?;
+        let background_rows = grid("background")?;
+        let attribute_rows = grid("attributes")?;
+        if lines.next() != Some("palette") {
+            return Err("missing palette section".to_string());
+        }
+        let palette: Vec<(char, String)> = lines
+            .filter_map(|l| {
+                let (key, name) = l.split_once(' ')?;
+                Some((key.chars().next()?, name.to_string()))
+            })
+            .collect();
+        let styles = |rows: Vec<String>| -> Result<Vec<Vec<String>>, String> {
+            rows.iter()
+                .map(|row| {
+                    if row.chars().count() != width as usize {
+                        return Err("style row does not match the width".to_string());
+                    }
+                    row.chars()
+                        .map(|k| {
+                            palette
+                                .iter()
+                                .find(|(key, _)| *key == k)
+                                .map(|(_, name)| name.clone())
+                                .ok_or(format!("unknown palette key {}", k))
+                        })
+                        .collect()
+                })
+                .collect()
+        };
+        Ok(Snapshot {
+            width,
+            height,
+            simbles: simble_rows.iter().map(|row| split_cells(row)).collect(),
+            foreground: styles(foreground_rows)?,
+            background: styles(background_rows)?,
+            attributes: styles(attribute_rows)?,
+        })
+    }
+    //coloured report of every cell that differs, None if the snapshots match
+    pub fn diff(&self, actual: &Snapshot) -> Option<String> {
+        if self == actual {
+            return None;
+        }
+        let mut out = String::new();
+        if (self.width, self.height) != (actual.width, actual.height) {
+            writeln!(
+                out,
+                "size differs: expected {}x{}, actual {}x{}",
+                self.width, self.height, actual.width, actual.height
+            )
+            .unwrap();
+            return Some(out);
+        }
+        let cell = |s: &Snapshot, x: usize, y: usize| {
+            (
+                s.simbles[y].get(x).cloned().unwrap_or_default(),
+                s.foreground[y][x].clone(),
+                s.background[y][x].clone(),
+                s.attributes[y][x].clone(),
+            )
+        };
+        let mut changes = Vec::new();
+        writeln!(out, "expected | actual").unwrap();
+        for y in 0..self.height as usize {
+            let mut expected_row = String::new();
+            let mut actual_row = String::new();
+            for x in 0..self.width as usize {
+                let expected = cell(self, x, y);
+                let found = cell(actual, x, y);
+                let (e, a) = (blank(&expected.0), blank(&found.0));
+                if expected != found {
+                    write!(expected_row, "\x1b[42m{}\x1b[0m", e).unwrap();
+                    write!(actual_row, "\x1b[41m{}\x1b[0m", a).unwrap();
+                    changes.push((x, y, expected, found));
+                } else {
+                    expected_row.push_str(e);
+                    actual_row.push_str(a);
+                }
+            }
+            writeln!(out, "|{}| |{}|", expected_row, actual_row).unwrap();
+        }
+        writeln!(out, "{} cells differ:", changes.len()).unwrap();
+        for (x, y, expected, found) in changes {
+            writeln!(
+                out,
+                "  ({}, {}) \x1b[32m{:?} fg {} bg {} {}\x1b[0m -> \x1b[31m{:?} fg {} bg {} {}\x1b[0m",
+                x,
+                y,
+                expected.0,
+                expected.1,
+                expected.2,
+                expected.3,
+                found.0,
+                found.1,
+                found.2,
+                found.3
+            )
+            .unwrap();
+        }
+        Some(out)
+    }
+}
+//attribute sets are written as [Bold, Italic] so they can't be confused with color names
+fn attributes_name(attributes: TextAttributes) -> String {
+    let names: Vec<String> = TextAttributes::SUPPORTED
+        .iter()
+        .filter(|a| attributes.has(**a))
+        .map(|a| format!("{:?}", a))
+        .collect();
+    format!("[{}]", names.join(", "))
+}
+fn split_cells(row: &str) -> Vec<String> {
+    Simble::from_text(row)
+        .iter()
+        .map(|s| s.as_str().to_string())
+        .collect()
+}
+fn blank(simble: &str) -> &str {
+    if simble.is_empty() {
+        " "
+    } else {
+        simble
+    }
+}
+pub fn snapshot_dir() -> PathBuf {
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap_or_else(|_| ".".to_string());
+    Path::new(&root).join("snapshots")
+}
+//compares the frame against snapshots/<name>.snap, run with SMALLTUI_BLESS=1 to store new output
+pub fn assert_snapshot<A, B, C, D>(name: &str, backend: &TestBackend<A, B, C, D>)
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+{
+    assert_snapshot_in(&snapshot_dir(), name, backend);
+}
+pub fn assert_snapshot_in<A, B, C, D>(dir: &Path, name: &str, backend: &TestBackend<A, B, C, D>)
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+{
+    let bless = env::var(BLESS_VAR).is_ok_and(|v| v == "1");
+    check_snapshot(dir, name, backend, bless);
+}
+fn check_snapshot<A, B, C, D>(
+    dir: &Path,
+    name: &str,
+    backend: &TestBackend<A, B, C, D>,
+    bless: bool,
+) where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+{
+    let actual = Snapshot::from_backend(backend);
+    let path = dir.join(format!("{}.snap", name));
+    if bless {
+        fs::create_dir_all(dir).expect("failed to create the snapshot directory");
+        fs::write(&path, actual.serialize()).expect("failed to write the snapshot");
+        return;
+    }
+    let stored = match fs::read_to_string(&path) {
+        Ok(stored) => stored,
+        Err(_) => panic!(
+            "snapshot {} not found, run with {}=1 to create it\n{}",
+            path.display(),
+            BLESS_VAR,
+            actual.serialize()
+        ),
+    };
+    let expected = Snapshot::parse(&stored)
+        .unwrap_or_else(|e| panic!("snapshot {} is malformed: {}", path.display(), e));
+    if let Some(diff) = expected.diff(&actual) {
+        panic!(
+            "snapshot {} does not match, run with {}=1 to accept the new output\n{}",
+            path.display(),
+            BLESS_VAR,
+            diff
+        );
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::{painter::Painter, painter::TextPainer, rect::Rect, test_backend::TB};
+    use crossterm::style::{Attribute, Color};
+
+    fn frame() -> TB {
+        let mut backend = TB::new(4, 2);
+        backend.write_text_line("ab", 1, 0);
+        backend.background_fill(Color::Blue.into(), Some(Rect::new(0, 1, 2, 1)));
+        backend.foreground_fill(
+            Color::Rgb { r: 1, g: 2, b: 3 }.into(),
+            Some(Rect::new(3, 0, 1, 1)),
+        );
+        backend.attributes_fill(
+            TextAttributes::from(Attribute::Bold).with(Attribute::Italic),
+            Some(Rect::new(1, 0, 1, 1)),
+        );
+        backend
+    }
+
+    #[test]
+    fn test_serialize() {
+        let snapshot = Snapshot::from_backend(&frame());
+        assert_eq!(
+            snapshot.serialize(),
+            "size 4x2
+simbles
+| ab |
+|    |
+foreground
+|aaab|
+|aaaa|
+background
+|cccc|
+|ddcc|
+attributes
+|efee|
+|eeee|
+palette
+a White
+b Rgb { r: 1, g: 2, b: 3 }
+c Black
+d Blue
+e []
+f [Bold, Italic]
+"
+        );
+    }
+
+    #[test]
+    fn test_parse_roundtrip() {
+        let snapshot = Snapshot::from_backend(&frame());
+        assert_eq!(Snapshot::parse(&snapshot.serialize()), Ok(snapshot));
+    }
+
+    #[test]
+    fn test_parse_wide_simbles() {
+        let mut backend = TB::new(4, 1);
+        backend.write_text_line("中a", 0, 0);
+        let snapshot = Snapshot::from_backend(&backend);
+        assert_eq!(Snapshot::parse(&snapshot.serialize()), Ok(snapshot));
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        assert!(Snapshot::parse("size 2x1\nsimbles\n|ab|\n").is_err());
+        assert!(Snapshot::parse("").is_err());
+    }
+
+    #[test]
+    fn test_diff() {
+        let expected = Snapshot::from_backend(&frame());
+        assert_eq!(expected.diff(&expected), None);
+        let mut changed = frame();
+        changed.write_text_line("c", 1, 0);
+        let diff = expected.diff(&Snapshot::from_backend(&changed)).unwrap();
+        assert!(diff.contains("1 cells differ"));
+        assert!(diff.contains("(1, 0)"));
+    }
+
+    #[test]
+    fn test_check_snapshot() {
+        let dir = env::temp_dir().join(format!("smalltui-snapshot-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("frame.snap"),
+            Snapshot::from_backend(&frame()).serialize(),
+        )
+        .unwrap();
+        check_snapshot(&dir, "frame", &frame(), false);
+        let mut changed = frame();
+        changed.background_fill(Color::Red.into(), None);
+        let mismatch = std::panic::catch_unwind(|| {
+            check_snapshot(&dir, "frame", &changed, false);
+        });
+        let missing = std::panic::catch_unwind(|| {
+            check_snapshot(&dir, "missing", &changed, false);
+        });
+        check_snapshot(&dir, "frame", &changed, true);
+        check_snapshot(&dir, "frame", &changed, false);
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(mismatch.is_err());
+        assert!(missing.is_err());
+    }
+}
@@ -1,32 +1,242 @@
 use super::{
-    buffer::Buffer, buffer_mediator::BufferMediator, painter::Painter, rect::Rect, BackgroundColor,
-    ForegroundColor, Simble,
+    buffer::{Buffer, VecBuffer},
+    buffer_mediator::BufferMediator,
+    hit_test::{Hit, HitMap},
+    painter::Painter,
+    rect::Rect,
+    widget::StatefulWidget,
+    BackgroundColor, ForegroundColor, Simble, TextAttributes,
 };
+use crate::error::Result;
 use crossterm::{
     cursor,
-    style::{self, Colors, SetColors},
+    event::{DisableMouseCapture, EnableMouseCapture},
+    style::{self, Attribute, Colors, SetAttribute, SetColors},
     terminal::{
-        disable_raw_mode, enable_raw_mode, Clear, ClearType, EnterAlternateScreen,
-        LeaveAlternateScreen,
+        disable_raw_mode, enable_raw_mode, BeginSynchronizedUpdate, Clear, ClearType,
+        EndSynchronizedUpdate, EnterAlternateScreen, LeaveAlternateScreen,
     },
     ExecutableCommand, QueueableCommand,
 };
-use std::io::{self, Write};
-pub struct TerminalWriter<A: Buffer<BackgroundColor>, B: Buffer<ForegroundColor>, C: Buffer<Simble>>
-{
+use std::{
+    env,
+    io::{self, IsTerminal, Stdout, Write},
+    panic,
+    sync::{
+        atomic::{AtomicBool, AtomicU32, Ordering},
+        Once,
+    },
+};
+//set while raw mode and the alternate screen are enabled, read by the panic hook and
+//the signal handlers to know if there is something to restore
+static TERMINAL_ACTIVE: AtomicBool = AtomicBool::new(false);
+//set when the terminal was given back and taken again, the next frame redraws everything
+static REDRAW_REQUESTED: AtomicBool = AtomicBool::new(false);
+//set while a writer wants mouse events, capture is turned off and on with the terminal
+static MOUSE_CAPTURED: AtomicBool = AtomicBool::new(false);
+//last row of the frame of a prepared inline viewport, an inline app never enters the
+//alternate screen and is given back by moving the cursor under its frame
+static INLINE_LAST_ROW: AtomicU32 = AtomicU32::new(FULLSCREEN);
+const FULLSCREEN: u32 = u32::MAX;
+//leaves the alternate screen or the inline frame, disables raw mode and shows the cursor if
+//a writer prepared the terminal, safe to call from a panic hook or a signal handler thread
+pub fn restore_terminal() -> io::Result<()> {
+    if !TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+        return Ok(());
+    }
+    leave_terminal(&mut io::stdout())
+}
+//takes the terminal back after restore_terminal and asks for a full redraw
+pub fn reenter_terminal() -> io::Result<()> {
+    enter_terminal(&mut io::stdout())?;
+    TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+    REDRAW_REQUESTED.store(true, Ordering::SeqCst);
+    Ok(())
+}
+fn leave_terminal(stream: &mut impl Write) -> io::Result<()> {
+    if MOUSE_CAPTURED.load(Ordering::SeqCst) {
+        stream.queue(DisableMouseCapture)?;
+    }
+    stream.queue(SetAttribute(Attribute::Reset))?;
+    stream.queue(style::ResetColor)?;
+    match INLINE_LAST_ROW.load(Ordering::SeqCst) {
+        FULLSCREEN => {
+            stream.queue(cursor::Show)?;
+            stream.queue(LeaveAlternateScreen)?;
+        }
+        last_row => {
+            stream.queue(cursor::MoveTo(0, last_row as u16))?;
+            stream.queue(style::Print("\r\n"))?;
+            stream.queue(cursor::Show)?;
+        }
+    }
+    stream.flush()?;
+    disable_raw_mode()
+}
+//an inline frame is placed again at the cursor by the next frame of its writer
+fn enter_terminal(stream: &mut impl Write) -> io::Result<()> {
+    if INLINE_LAST_ROW.load(Ordering::SeqCst) == FULLSCREEN {
+        stream.queue(EnterAlternateScreen)?;
+    }
+    if MOUSE_CAPTURED.load(Ordering::SeqCst) {
+        stream.queue(EnableMouseCapture)?;
+    }
+    stream.flush()?;
+    enable_raw_mode()
+}
+//restores the terminal before the previous panic hook prints the message,
+//installing it more than once has no effect
+pub fn install_panic_hook() {
+    static INSTALLED: Once = Once::new();
+    INSTALLED.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let _ = restore_terminal();
+            previous(info);
+        }));
+    });
+}
+//restores the terminal on SIGINT, SIGTERM and SIGTSTP before the default action runs,
+//and takes it back on SIGCONT if it was suspended, installing them more than once has no
+//effect and only the first call reports errors
+#[cfg(unix)]
+pub fn install_signal_handlers() -> io::Result<()> {
+    static INSTALLED: Once = Once::new();
+    let mut result = Ok(());
+    INSTALLED.call_once(|| result = spawn_signal_thread());
+    result
+}
+#[cfg(unix)]
+fn spawn_signal_thread() -> io::Result<()> {
+    use signal_hook::{
+        consts::{SIGCONT, SIGINT, SIGTERM, SIGTSTP},
+        iterator::Signals,
+        low_level,
+    };
+    let mut signals = Signals::new([SIGINT, SIGTERM, SIGTSTP, SIGCONT])?;
+    std::thread::Builder::new()
+        .name("smalltui-signals".to_owned())
+        .spawn(move || {
+            //whether the terminal was ours when the process got stopped
+            let mut suspended = false;
+            for signal in signals.forever() {
+                match signal {
+                    SIGTSTP => {
+                        suspended = TERMINAL_ACTIVE.load(Ordering::SeqCst);
+                        let _ = restore_terminal();
+                        let _ = low_level::emulate_default_handler(SIGTSTP);
+                    }
+                    SIGCONT => {
+                        if std::mem::take(&mut suspended) {
+                            let _ = reenter_terminal();
+                        }
+                    }
+                    _ => {
+                        let _ = restore_terminal();
+                        let _ = low_level::emulate_default_handler(signal);
+                    }
+                }
+            }
+        })?;
+    Ok(())
+}
+#[cfg(not(unix))]
+pub fn install_signal_handlers() -> io::Result<()> {
+    Ok(())
+}
+//whether frames are wrapped in begin/end synchronized update sequences (DEC mode 2026)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SynchronizedOutput {
+    Never,
+    Always,
+    //only when writing to a terminal that is known to support it
+    #[default]
+    Auto,
+}
+//guess from the environment if the terminal understands synchronized updates
+pub fn detect_synchronized_output() -> bool {
+    const PROGRAMS: [&str; 6] = [
+        "WezTerm",
+        "iTerm.app",
+        "vscode",
+        "ghostty",
+        "contour",
+        "rio",
+    ];
+    const TERMS: [&str; 6] = [
+        "kitty",
+        "foot",
+        "alacritty",
+        "wezterm",
+        "ghostty",
+        "contour",
+    ];
+    if env::var_os("KITTY_WINDOW_ID").is_some() || env::var_os("WEZTERM_EXECUTABLE").is_some() {
+        return true;
+    }
+    if let Ok(program) = env::var("TERM_PROGRAM") {
+        if PROGRAMS.contains(&program.as_str()) {
+            return true;
+        }
+    }
+    if let Ok(term) = env::var("TERM") {
+        return TERMS.iter().any(|t| term.contains(t));
+    }
+    false
+}
+//where frames are drawn, the whole screen or a band of lines below the cursor
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Viewport {
+    //the alternate screen, left when the writer is dropped
+    #[default]
+    Fullscreen,
+    //this many lines on the normal screen, the last frame stays in place when dropped
+    Inline(u16),
+}
+pub struct TerminalWriter<
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes> = VecBuffer<TextAttributes>,
+    W: Write = Stdout,
+> {
     background: A,
     previous_background: A,
     foreground: B,
     previous_foreground: B,
     text: C,
     previous_text: C,
-    stream: std::io::Stdout,
+    attributes: D,
+    previous_attributes: D,
+    //attributes the terminal is currently set to, they persist between frames
+    active_attributes: TextAttributes,
+    stream: W,
     mediator_stack: Vec<BufferMediator>,
+    //raw mode and the alternate screen are only touched when the sink is a real terminal
+    tty: bool,
+    prepared: bool,
+    synchronized_output: SynchronizedOutput,
+    synchronized: bool,
+    //clear the screen and write every cell on the next frame
+    full_redraw: bool,
+    viewport: Viewport,
+    //height of the terminal and first row of the viewport on it
+    screen_height: u16,
+    origin_y: u16,
+    mouse_capture: bool,
+    //widgets rendered since the last frame, and the ones of the last frame for hit tests
+    hit_map: HitMap,
+    last_hit_map: HitMap,
 }
-impl<A: Buffer<BackgroundColor>, B: Buffer<ForegroundColor>, C: Buffer<Simble>>
-    TerminalWriter<A, B, C>
+impl<A, B, C, D, W> TerminalWriter<A, B, C, D, W>
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+    W: Write,
 {
-    pub fn new(width: u16, height: u16, stream: std::io::Stdout) -> TerminalWriter<A, B, C> {
+    pub fn new(width: u16, height: u16, stream: W) -> TerminalWriter<A, B, C, D, W> {
         TerminalWriter {
             background: A::new(width, height),
             previous_background: A::new(width, height),
@@ -34,58 +244,405 @@ impl<A: Buffer<BackgroundColor>, B: Buffer<ForegroundColor>, C: Buffer<Simble>>
             previous_foreground: B::new(width, height),
             text: C::new(width, height),
             previous_text: C::new(width, height),
+            attributes: D::new(width, height),
+            previous_attributes: D::new(width, height),
+            active_attributes: TextAttributes::default(),
             stream,
             mediator_stack: Vec::new(),
+            tty: false,
+            prepared: false,
+            synchronized_output: SynchronizedOutput::default(),
+            synchronized: false,
+            full_redraw: false,
+            viewport: Viewport::default(),
+            screen_height: height,
+            origin_y: 0,
+            mouse_capture: false,
+            hit_map: HitMap::new(),
+            last_hit_map: HitMap::new(),
+        }
+    }
+    pub fn synchronized_output(&self) -> SynchronizedOutput {
+        self.synchronized_output
+    }
+    pub fn set_synchronized_output(&mut self, mode: SynchronizedOutput) {
+        self.synchronized_output = mode;
+        self.synchronized = match mode {
+            SynchronizedOutput::Never => false,
+            SynchronizedOutput::Always => true,
+            SynchronizedOutput::Auto => self.tty && detect_synchronized_output(),
+        };
+    }
+    //true if the next frames will be wrapped in synchronized update sequences
+    pub fn uses_synchronized_output(&self) -> bool {
+        self.synchronized
+    }
+    pub fn viewport(&self) -> Viewport {
+        self.viewport
+    }
+    //to be set before prepare_area, an inline viewport makes the frame as tall as its lines
+    pub fn set_viewport(&mut self, viewport: Viewport) {
+        self.viewport = viewport;
+        let width = self.text.area().width;
+        self.resize_buffers(width, self.screen_height);
+    }
+    //first row of the terminal covered by the frame
+    pub fn origin_y(&self) -> u16 {
+        self.origin_y
+    }
+    pub fn mouse_capture(&self) -> bool {
+        self.mouse_capture
+    }
+    //asks the terminal to report mouse events, only has an effect on a real terminal
+    pub fn set_mouse_capture(&mut self, enabled: bool) -> Result<()> {
+        if !self.tty || self.mouse_capture == enabled {
+            self.mouse_capture = enabled;
+            return Ok(());
+        }
+        self.mouse_capture = enabled;
+        MOUSE_CAPTURED.store(enabled, Ordering::SeqCst);
+        if self.prepared && TERMINAL_ACTIVE.load(Ordering::SeqCst) {
+            if enabled {
+                self.stream.execute(EnableMouseCapture)?;
+            } else {
+                self.stream.execute(DisableMouseCapture)?;
+            }
         }
+        Ok(())
+    }
+    //widgets of the last flushed frame
+    pub fn last_hit_map(&self) -> &HitMap {
+        &self.last_hit_map
+    }
+    //the innermost widget of the last flushed frame under a point of the terminal
+    pub fn hit_test(&self, x: u16, y: u16) -> Option<Hit> {
+        let y = y.checked_sub(self.origin_y)?;
+        self.last_hit_map.hit_test(x, y)
+    }
+    pub fn is_tty(&self) -> bool {
+        self.tty
+    }
+    pub fn stream(&self) -> &W {
+        &self.stream
     }
-    pub fn prepare_area(&mut self) {
-        self.stream.execute(EnterAlternateScreen).unwrap();
-        enable_raw_mode().unwrap();
-        self.clear();
+    pub fn stream_mut(&mut self) -> &mut W {
+        &mut self.stream
     }
-    pub fn clear(&mut self) {
-        self.stream.execute(Clear(ClearType::All)).unwrap();
+    pub fn prepare_area(&mut self) -> Result<()> {
+        self.enter()?;
+        if self.tty {
+            self.prepared = true;
+            self.publish_viewport();
+            TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        }
+        self.clear()
+    }
+    //clears the viewport, an inline viewport keeps what is above it
+    pub fn clear(&mut self) -> Result<()> {
+        self.queue_clear()?;
+        self.stream.flush()?;
+        Ok(())
     }
-    pub fn flush_frame(&mut self) -> io::Result<()> {
+    fn queue_clear(&mut self) -> io::Result<()> {
+        match self.viewport {
+            Viewport::Fullscreen => {
+                self.stream.queue(Clear(ClearType::All))?;
+            }
+            Viewport::Inline(_) => {
+                self.stream.queue(cursor::MoveTo(0, self.origin_y))?;
+                self.stream.queue(Clear(ClearType::FromCursorDown))?;
+            }
+        }
+        Ok(())
+    }
+    //takes the terminal, raw mode is only enabled on a real terminal
+    fn enter(&mut self) -> Result<()> {
+        match self.viewport {
+            Viewport::Fullscreen if self.tty => enter_terminal(&mut self.stream)?,
+            Viewport::Fullscreen => {}
+            Viewport::Inline(_) => {
+                let row = if self.tty {
+                    enable_raw_mode()?;
+                    if MOUSE_CAPTURED.load(Ordering::SeqCst) {
+                        self.stream.queue(EnableMouseCapture)?;
+                    }
+                    cursor::position()?.1
+                } else {
+                    0
+                };
+                self.reserve_lines(row)?;
+            }
+        }
+        Ok(())
+    }
+    //gives the terminal back, an inline viewport leaves its frame and moves the cursor under it
+    fn leave(&mut self) -> Result<()> {
+        match self.viewport {
+            Viewport::Fullscreen if self.tty => leave_terminal(&mut self.stream)?,
+            Viewport::Fullscreen => {}
+            Viewport::Inline(_) => {
+                let last_row = self.origin_y + self.text.area().height.saturating_sub(1);
+                if self.tty && MOUSE_CAPTURED.load(Ordering::SeqCst) {
+                    self.stream.queue(DisableMouseCapture)?;
+                }
+                self.stream.queue(SetAttribute(Attribute::Reset))?;
+                self.stream.queue(style::ResetColor)?;
+                self.stream.queue(cursor::MoveTo(0, last_row))?;
+                self.stream.queue(style::Print("\r\n"))?;
+                self.stream.queue(cursor::Show)?;
+                self.stream.flush()?;
+                self.active_attributes = TextAttributes::none();
+                if self.tty {
+                    disable_raw_mode()?;
+                }
+            }
+        }
+        Ok(())
+    }
+    //makes room for the inline viewport starting at the row of the cursor, scrolling the
+    //screen up when there are not enough lines below it
+    fn reserve_lines(&mut self, row: u16) -> io::Result<()> {
+        let lines = self.text.area().height;
+        let newlines = "\n".repeat(lines.saturating_sub(1) as usize);
+        self.stream.queue(style::Print(newlines))?;
+        self.origin_y = row.min(self.screen_height.saturating_sub(lines));
+        self.publish_viewport();
+        Ok(())
+    }
+    //tells restore_terminal where the frame is, it has no access to the writer
+    fn publish_viewport(&self) {
+        if !self.prepared {
+            return;
+        }
+        let last_row = match self.viewport {
+            Viewport::Fullscreen => FULLSCREEN,
+            Viewport::Inline(_) => {
+                (self.origin_y + self.text.area().height.saturating_sub(1)) as u32
+            }
+        };
+        INLINE_LAST_ROW.store(last_row, Ordering::SeqCst);
+    }
+    //prints lines above an inline viewport, they scroll into the scrollback like regular output
+    //and the viewport is redrawn under them, ignored in fullscreen
+    pub fn insert_before(&mut self, text: &str) -> Result<()> {
+        if self.viewport == Viewport::Fullscreen {
+            return Ok(());
+        }
+        self.queue_clear()?;
+        self.stream.queue(SetAttribute(Attribute::Reset))?;
+        self.stream.queue(style::ResetColor)?;
+        self.active_attributes = TextAttributes::none();
+        let mut printed = 0u16;
+        for line in text.lines() {
+            self.stream.queue(style::Print(line))?;
+            self.stream.queue(style::Print("\r\n"))?;
+            printed = printed.saturating_add(1);
+        }
+        let row = self
+            .origin_y
+            .saturating_add(printed)
+            .min(self.screen_height.saturating_sub(1));
+        self.reserve_lines(row)?;
+        self.stream.flush()?;
+        self.request_full_redraw();
+        Ok(())
+    }
+    //gives the terminal back in cooked mode on the normal screen until the guard is dropped,
+    //to run programs like an editor or a pager
+    pub fn suspend(&mut self) -> Result<SuspendGuard<'_, A, B, C, D, W>> {
+        let active = self.prepared && TERMINAL_ACTIVE.swap(false, Ordering::SeqCst);
+        if active {
+            self.leave()?;
+        }
+        Ok(SuspendGuard {
+            writer: self,
+            active,
+            resumed: false,
+        })
+    }
+    //the next frame is written in full, for when the screen content is unknown
+    pub fn request_full_redraw(&mut self) {
+        self.full_redraw = true;
+    }
+    pub fn flush_frame(&mut self) -> Result<()> {
         let area = self.background.area();
-        let stdout = &mut self.stream;
+        self.last_hit_map = std::mem::take(&mut self.hit_map);
+        let reentered = self.prepared && REDRAW_REQUESTED.swap(false, Ordering::SeqCst);
+        if reentered && self.tty && self.viewport != Viewport::Fullscreen {
+            //the shell wrote under the frame while the process was stopped
+            self.reserve_lines(cursor::position()?.1)?;
+        }
+        let full_redraw = std::mem::take(&mut self.full_redraw) || reentered;
+        //state of the terminal as left by the commands queued so far in this frame
+        let mut position: Option<(u16, u16)> = None;
+        let mut active_foreground: Option<ForegroundColor> = None;
+        let mut active_background: Option<BackgroundColor> = None;
+        //changed cells that are contiguous and share colors are printed as a single string
+        let mut run = String::new();
+        let mut began = false;
+        if full_redraw {
+            if self.synchronized {
+                self.stream.queue(BeginSynchronizedUpdate)?;
+                began = true;
+            }
+            self.stream.queue(SetAttribute(Attribute::Reset))?;
+            self.queue_clear()?;
+            self.active_attributes = TextAttributes::none();
+        }
         for y in 0..area.height {
             for x in 0..area.width {
-                let new = (
-                    self.background[(x, y)],
-                    self.foreground[(x, y)],
-                    self.text[(x, y)].clone(),
-                );
-                let previous = (
-                    self.previous_background[(x, y)],
-                    self.previous_foreground[(x, y)],
-                    self.previous_text[(x, y)].clone(),
-                );
-                if new != previous {
-                    stdout
-                        .queue(cursor::MoveTo(x, y))?
-                        .queue(SetColors(Colors::new(new.1 .0, new.0 .0)))?
-                        .queue(style::Print(new.2 .0))?;
+                let text = &self.text[(x, y)];
+                //the right half of a wide simble is drawn by the simble on its left
+                if text.is_continuation() && x > 0 && self.text[(x - 1, y)].width() > 1 {
+                    continue;
+                }
+                //a wide simble is only drawn if its right half was not overwritten or clipped
+                let wide = text.width() > 1;
+                let whole =
+                    !wide || (x + 1 < area.width && self.text[(x + 1, y)].is_continuation());
+                let changed = full_redraw
+                    || self.cell_changed(x, y)
+                    || (wide && x + 1 < area.width && self.cell_changed(x + 1, y));
+                if !changed {
+                    continue;
+                }
+                let background = self.background[(x, y)];
+                let foreground = self.foreground[(x, y)];
+                let attributes = self.attributes[(x, y)];
+                let stdout = &mut self.stream;
+                if self.synchronized && !began {
+                    stdout.queue(BeginSynchronizedUpdate)?;
+                    began = true;
+                }
+                if position != Some((x, y)) {
+                    Self::print_run(stdout, &mut run)?;
+                    stdout.queue(cursor::MoveTo(x, y + self.origin_y))?;
+                }
+                if attributes != self.active_attributes {
+                    Self::print_run(stdout, &mut run)?;
+                    Self::set_attributes(stdout, self.active_attributes, attributes)?;
+                    self.active_attributes = attributes;
+                }
+                let foreground_changed = active_foreground != Some(foreground);
+                let background_changed = active_background != Some(background);
+                if foreground_changed || background_changed {
+                    Self::print_run(stdout, &mut run)?;
+                    let colors = match (foreground_changed, background_changed) {
+                        (true, true) => Colors::new(foreground.0, background.0),
+                        (true, false) => Colors {
+                            foreground: Some(foreground.0),
+                            background: None,
+                        },
+                        _ => Colors {
+                            foreground: None,
+                            background: Some(background.0),
+                        },
+                    };
+                    stdout.queue(SetColors(colors))?;
+                    active_foreground = Some(foreground);
+                    active_background = Some(background);
+                }
+                if text.is_continuation() || !whole {
+                    run.push(' ');
+                    position = Some((x + 1, y));
+                } else {
+                    run.push_str(text.0.as_str());
+                    position = Some((x + text.width(), y));
                 }
             }
         }
+        let stdout = &mut self.stream;
+        Self::print_run(stdout, &mut run)?;
+        if began {
+            stdout.queue(EndSynchronizedUpdate)?;
+        }
         stdout.flush()?;
         std::mem::swap(&mut self.background, &mut self.previous_background);
         std::mem::swap(&mut self.foreground, &mut self.previous_foreground);
         std::mem::swap(&mut self.text, &mut self.previous_text);
+        std::mem::swap(&mut self.attributes, &mut self.previous_attributes);
         self.background.reset();
         self.foreground.reset();
         self.text.reset();
+        self.attributes.reset();
         Ok(())
     }
-    pub fn resize(&mut self, width: u16, height: u16) {
+    fn cell_changed(&self, x: u16, y: u16) -> bool {
+        self.background[(x, y)] != self.previous_background[(x, y)]
+            || self.foreground[(x, y)] != self.previous_foreground[(x, y)]
+            || self.text[(x, y)] != self.previous_text[(x, y)]
+            || self.attributes[(x, y)] != self.previous_attributes[(x, y)]
+    }
+    fn print_run(stream: &mut W, run: &mut String) -> io::Result<()> {
+        if !run.is_empty() {
+            stream.queue(style::Print(run.as_str()))?;
+            run.clear();
+        }
+        Ok(())
+    }
+    //emits only the sgr codes needed to go from the current attributes to the new ones
+    fn set_attributes(
+        stream: &mut W,
+        current: TextAttributes,
+        new: TextAttributes,
+    ) -> io::Result<()> {
+        const OFF: [(Attribute, Attribute); 7] = [
+            (Attribute::Italic, Attribute::NoItalic),
+            (Attribute::Underlined, Attribute::NoUnderline),
+            (Attribute::SlowBlink, Attribute::NoBlink),
+            (Attribute::RapidBlink, Attribute::NoBlink),
+            (Attribute::Reverse, Attribute::NoReverse),
+            (Attribute::Hidden, Attribute::NoHidden),
+            (Attribute::CrossedOut, Attribute::NotCrossedOut),
+        ];
+        let mut current = current;
+        //bold and dim can only be turned off together
+        if (current.has(Attribute::Bold) && !new.has(Attribute::Bold))
+            || (current.has(Attribute::Dim) && !new.has(Attribute::Dim))
+        {
+            stream.queue(SetAttribute(Attribute::NormalIntensity))?;
+            current = current.without(Attribute::Bold).without(Attribute::Dim);
+        }
+        for (on, off) in OFF {
+            if current.has(on) && !new.has(on) {
+                stream.queue(SetAttribute(off))?;
+                //blink has two variants sharing the same off code
+                if off == Attribute::NoBlink {
+                    current = current
+                        .without(Attribute::SlowBlink)
+                        .without(Attribute::RapidBlink);
+                }
+                current = current.without(on);
+            }
+        }
+        for attribute in TextAttributes::SUPPORTED {
+            if new.has(attribute) && !current.has(attribute) {
+                stream.queue(SetAttribute(attribute))?;
+            }
+        }
+        Ok(())
+    }
+    //takes the size of the terminal, an inline viewport keeps its number of lines
+    pub fn resize(&mut self, width: u16, height: u16) -> Result<()> {
+        self.resize_buffers(width, height);
+        self.clear()
+    }
+    fn resize_buffers(&mut self, width: u16, screen_height: u16) {
+        self.screen_height = screen_height;
+        let height = match self.viewport {
+            Viewport::Fullscreen => screen_height,
+            Viewport::Inline(lines) => lines.min(screen_height),
+        };
+        self.origin_y = self.origin_y.min(screen_height - height);
         self.background.resize(width, height);
         self.previous_background.resize(width, height);
         self.foreground.resize(width, height);
         self.previous_foreground.resize(width, height);
         self.text.resize(width, height);
         self.previous_text.resize(width, height);
-        self.clear();
+        self.attributes.resize(width, height);
+        self.previous_attributes.resize(width, height);
+        self.publish_viewport();
     }
     fn fill<T: Default + Sized + Clone + Send + Sync>(
         mediator: &BufferMediator,
@@ -100,18 +657,98 @@ impl<A: Buffer<BackgroundColor>, B: Buffer<ForegroundColor>, C: Buffer<Simble>>
         mediator.write(fill.as_slice(), area, buff);
     }
 }
-impl<A: Buffer<BackgroundColor>, B: Buffer<ForegroundColor>, C: Buffer<Simble>> Drop
-    for TerminalWriter<A, B, C>
+impl<A, B, C, D, W> TerminalWriter<A, B, C, D, W>
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+    W: Write + IsTerminal,
+{
+    //like new but enables raw mode and the alternate screen if the stream is attached to a terminal
+    pub fn new_terminal(width: u16, height: u16, stream: W) -> TerminalWriter<A, B, C, D, W> {
+        let tty = stream.is_terminal();
+        let mut writer = Self::new(width, height, stream);
+        writer.tty = tty;
+        writer.set_synchronized_output(writer.synchronized_output);
+        writer
+    }
+}
+//returned by TerminalWriter::suspend, takes the terminal back when dropped or resumed
+pub struct SuspendGuard<'a, A, B, C, D, W>
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+    W: Write,
+{
+    writer: &'a mut TerminalWriter<A, B, C, D, W>,
+    //whether the terminal was prepared when suspended and has to be entered again
+    active: bool,
+    resumed: bool,
+}
+impl<A, B, C, D, W> SuspendGuard<'_, A, B, C, D, W>
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+    W: Write,
+{
+    //like dropping the guard but reports errors
+    pub fn resume(mut self) -> Result<()> {
+        self.take_back()
+    }
+    fn take_back(&mut self) -> Result<()> {
+        if std::mem::replace(&mut self.resumed, true) {
+            return Ok(());
+        }
+        //the screen is repainted in full whatever happens, its content is unknown
+        self.writer.request_full_redraw();
+        if self.active {
+            self.writer.enter()?;
+            TERMINAL_ACTIVE.store(true, Ordering::SeqCst);
+        }
+        self.writer.clear()
+    }
+}
+impl<A, B, C, D, W> Drop for SuspendGuard<'_, A, B, C, D, W>
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+    W: Write,
+{
+    fn drop(&mut self) {
+        let _ = self.take_back();
+    }
+}
+impl<A, B, C, D, W> Drop for TerminalWriter<A, B, C, D, W>
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+    W: Write,
 {
+    //errors are ignored, there is no one left to report them to
     fn drop(&mut self) {
-        self.stream.queue(style::ResetColor).unwrap();
-        self.stream.queue(LeaveAlternateScreen).unwrap();
-        disable_raw_mode().unwrap();
-        self.stream.flush().unwrap();
+        //the panic hook or a signal may have restored the terminal already
+        if self.prepared && TERMINAL_ACTIVE.swap(false, Ordering::SeqCst) {
+            let _ = self.leave();
+        }
+        let _ = self.stream.flush();
     }
 }
-impl<A: Buffer<BackgroundColor>, B: Buffer<ForegroundColor>, C: Buffer<Simble>> Painter
-    for TerminalWriter<A, B, C>
+impl<A, B, C, D, W> Painter for TerminalWriter<A, B, C, D, W>
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+    W: Write + Send + Sync,
 {
     fn background_fill(&mut self, color: BackgroundColor, area: Option<super::rect::Rect>) {
         let default_mediator = BufferMediator::new(self.background.area(), 0, 0);
@@ -145,6 +782,16 @@ impl<A: Buffer<BackgroundColor>, B: Buffer<ForegroundColor>, C: Buffer<Simble>>
         );
     }
 
+    fn attributes_fill(&mut self, attributes: TextAttributes, area: Option<super::rect::Rect>) {
+        let default_mediator = BufferMediator::new(self.background.area(), 0, 0);
+        Self::fill::<TextAttributes>(
+            self.mediator_stack.last().unwrap_or(&default_mediator),
+            attributes,
+            area,
+            &mut self.attributes,
+        );
+    }
+
     fn write_simbles(&mut self, text: &[Simble], area: super::rect::Rect) {
         let default_mediator = BufferMediator::new(self.background.area(), 0, 0);
         let mediator = &self.mediator_stack.last().unwrap_or(&default_mediator);
@@ -163,6 +810,12 @@ impl<A: Buffer<BackgroundColor>, B: Buffer<ForegroundColor>, C: Buffer<Simble>>
         mediator.write(color, area, &mut self.foreground);
     }
 
+    fn write_attributes(&mut self, attributes: &[TextAttributes], area: super::rect::Rect) {
+        let default_mediator = BufferMediator::new(self.background.area(), 0, 0);
+        let mediator = &self.mediator_stack.last().unwrap_or(&default_mediator);
+        mediator.write(attributes, area, &mut self.attributes);
+    }
+
     fn area(&self) -> super::rect::Rect {
         let default_mediator = BufferMediator::new(self.background.area(), 0, 0);
         let mediator = &self.mediator_stack.last().unwrap_or(&default_mediator);
@@ -171,33 +824,58 @@ impl<A: Buffer<BackgroundColor>, B: Buffer<ForegroundColor>, C: Buffer<Simble>>
 
     fn render_widget(
         &mut self,
-        widget: &dyn super::widget::Widget<TerminalWriter<A, B, C>>,
+        widget: &dyn super::widget::Widget<TerminalWriter<A, B, C, D, W>>,
         area: super::rect::Rect,
         scroll_x: u16,
         scroll_y: u16,
     ) {
         let default_mediator = BufferMediator::new(self.background.area(), 0, 0);
         let mediator = self.mediator_stack.last().unwrap_or(&default_mediator);
-        self.mediator_stack
-            .push(mediator.generate_inner(&area, scroll_x, scroll_y));
+        let inner = mediator.generate_inner(&area, scroll_x, scroll_y);
+        self.hit_map.enter(widget.id(), area, inner);
+        self.mediator_stack.push(inner);
         widget.render_widget(self);
         self.mediator_stack.pop();
+        self.hit_map.leave();
+    }
+
+    fn render_stateful_widget<T: StatefulWidget<Self> + ?Sized>(
+        &mut self,
+        widget: &T,
+        state: &mut T::State,
+        area: Rect,
+        scroll_x: u16,
+        scroll_y: u16,
+    ) {
+        let default_mediator = BufferMediator::new(self.background.area(), 0, 0);
+        let mediator = self.mediator_stack.last().unwrap_or(&default_mediator);
+        let inner = mediator.generate_inner(&area, scroll_x, scroll_y);
+        self.hit_map.enter(widget.id(), area, inner);
+        self.mediator_stack.push(inner);
+        widget.render_stateful_widget(self, state);
+        self.mediator_stack.pop();
+        self.hit_map.leave();
     }
 }
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::renderer::buffer::VecBuffer;
+    use crate::renderer::hit_test::WidgetId;
+    use crate::renderer::painter::TextPainer;
     use crate::renderer::widget::Widget;
     use compact_str::ToCompactString;
     use crossterm::style::Color;
-    use std::io::stdout;
-    use std::io::{self, Write};
-    type TW =
-        TerminalWriter<VecBuffer<BackgroundColor>, VecBuffer<ForegroundColor>, VecBuffer<Simble>>;
+    type TW = TerminalWriter<
+        VecBuffer<BackgroundColor>,
+        VecBuffer<ForegroundColor>,
+        VecBuffer<Simble>,
+        VecBuffer<TextAttributes>,
+        Vec<u8>,
+    >;
     #[test]
     fn test_new() {
-        let writer: TW = TerminalWriter::new(80, 25, stdout());
+        let writer: TW = TerminalWriter::new(80, 25, Vec::new());
         assert_eq!(writer.background.area(), Rect::new(0, 0, 80, 25));
         assert_eq!(writer.foreground.area(), Rect::new(0, 0, 80, 25));
         assert_eq!(writer.text.area(), Rect::new(0, 0, 80, 25));
@@ -205,29 +883,277 @@ mod tests {
 
     #[test]
     fn test_prepare_area() {
-        let mut writer: TW = TerminalWriter::new(80, 25, stdout());
-        writer.prepare_area();
-        // Expect that the terminal switches to alternate screen mode and clears the screen
+        let mut writer: TW = TerminalWriter::new(80, 25, Vec::new());
+        writer.prepare_area().unwrap();
+        // a plain writer is not a tty so only the clear sequence is emitted
+        assert!(!writer.is_tty());
+        assert_eq!(writer.stream().as_slice(), b"\x1b[2J");
     }
 
     #[test]
     fn test_clear() {
-        let mut writer: TW = TerminalWriter::new(80, 25, stdout());
-        writer.clear();
-        // Expect that the screen is cleared
+        let mut writer: TW = TerminalWriter::new(80, 25, Vec::new());
+        writer.clear().unwrap();
+        assert_eq!(writer.stream().as_slice(), b"\x1b[2J");
     }
 
     #[test]
     fn test_flush_frame() {
-        let mut writer: TW = TerminalWriter::new(80, 25, stdout());
+        let mut writer: TW = TerminalWriter::new(80, 25, Vec::new());
         assert!(writer.flush_frame().is_ok());
-        // Expect the frame to be flushed without errors
+        // nothing changed since the last frame so nothing should be written
+        assert!(writer.stream().is_empty());
+    }
+
+    #[test]
+    fn test_flush_frame_output() {
+        let mut writer: TW = TerminalWriter::new(80, 25, Vec::new());
+        writer.simble_fill(Simble('A'.to_compact_string()), Some(Rect::new(3, 2, 1, 1)));
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(output, "\x1b[3;4H\x1b[38;5;15;48;5;0mA");
+    }
+
+    #[test]
+    fn test_flush_frame_batches_runs() {
+        let mut writer: TW = TerminalWriter::new(10, 3, Vec::new());
+        writer.write_text_line("Hello", 1, 1);
+        writer.write_text_line("you", 7, 1);
+        writer.foreground_fill(ForegroundColor(Color::Red), Some(Rect::new(4, 1, 1, 1)));
+        writer.background_fill(BackgroundColor(Color::Blue), Some(Rect::new(7, 1, 3, 1)));
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(
+            output,
+            "\x1b[2;2H\x1b[38;5;15;48;5;0mHel\x1b[38;5;9ml\x1b[38;5;15mo\x1b[2;8H\x1b[48;5;12myou"
+        );
+    }
+
+    #[test]
+    fn test_flush_frame_only_changes() {
+        let mut writer: TW = TerminalWriter::new(10, 3, Vec::new());
+        writer.write_text_line("Hello", 1, 1);
+        writer.flush_frame().unwrap();
+        writer.stream_mut().clear();
+        writer.write_text_line("Hallo", 1, 1);
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(output, "\x1b[2;3H\x1b[38;5;15;48;5;0ma");
+    }
+
+    #[test]
+    fn test_full_redraw() {
+        let mut writer: TW = TerminalWriter::new(3, 1, Vec::new());
+        writer.write_text_line("ab", 0, 0);
+        writer.flush_frame().unwrap();
+        writer.stream_mut().clear();
+        writer.write_text_line("ab", 0, 0);
+        writer.request_full_redraw();
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(output, "\x1b[0m\x1b[2J\x1b[1;1H\x1b[38;5;15;48;5;0mab ");
+        writer.stream_mut().clear();
+        writer.write_text_line("ab", 0, 0);
+        writer.flush_frame().unwrap();
+        assert!(writer.stream().is_empty());
+    }
+
+    #[test]
+    fn test_inline_viewport() {
+        let mut writer: TW = TerminalWriter::new(3, 5, Vec::new());
+        writer.set_viewport(Viewport::Inline(2));
+        assert_eq!(writer.text.area(), Rect::new(0, 0, 3, 2));
+        writer.prepare_area().unwrap();
+        writer.stream_mut().clear();
+        writer.write_text_line("ab", 0, 1);
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert!(output.contains("\x1b[2;1H"));
+        assert!(!output.contains("\x1b[2J"));
+    }
+
+    #[test]
+    fn test_insert_before_moves_viewport_down() {
+        let mut writer: TW = TerminalWriter::new(3, 5, Vec::new());
+        writer.set_viewport(Viewport::Inline(2));
+        writer.prepare_area().unwrap();
+        writer.flush_frame().unwrap();
+        writer.stream_mut().clear();
+        writer.insert_before("l1\nl2").unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(output, "\x1b[1;1H\x1b[J\x1b[0m\x1b[0ml1\r\nl2\r\n\n");
+        assert_eq!(writer.origin_y(), 2);
+        writer.stream_mut().clear();
+        writer.write_text_line("ab", 0, 0);
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert!(output.starts_with("\x1b[0m\x1b[3;1H\x1b[J\x1b[3;1H"));
+        //the viewport never goes past the bottom of the screen
+        writer.insert_before("l3\nl4\nl5").unwrap();
+        assert_eq!(writer.origin_y(), 3);
+    }
+
+    #[test]
+    fn test_insert_before_fullscreen_is_ignored() {
+        let mut writer: TW = TerminalWriter::new(3, 5, Vec::new());
+        writer.insert_before("log").unwrap();
+        assert!(writer.stream().is_empty());
+    }
+
+    #[test]
+    fn test_restore_inline_viewport() {
+        INLINE_LAST_ROW.store(4, Ordering::SeqCst);
+        let mut output = Vec::new();
+        let _ = leave_terminal(&mut output);
+        INLINE_LAST_ROW.store(FULLSCREEN, Ordering::SeqCst);
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.ends_with("\x1b[5;1H\r\n\x1b[?25h"));
+        assert!(!output.contains("\x1b[?1049l"));
+    }
+
+    #[test]
+    fn test_hit_test_uses_last_frame() {
+        struct Parent;
+        impl Widget<TW> for Parent {
+            fn render_widget(&self, painter: &mut TW) {
+                painter.render_widget(&Child, Rect::new(0, 2, 2, 1), 0, 0);
+            }
+            fn id(&self) -> Option<WidgetId> {
+                Some(WidgetId(1))
+            }
+        }
+        struct Child;
+        impl Widget<TW> for Child {
+            fn render_widget(&self, _painter: &mut TW) {}
+            fn id(&self) -> Option<WidgetId> {
+                Some(WidgetId(2))
+            }
+        }
+        let mut writer: TW = TerminalWriter::new(10, 5, Vec::new());
+        writer.render_widget(&Parent, Rect::new(1, 1, 4, 3), 0, 1);
+        assert_eq!(writer.hit_test(1, 2), None);
+        writer.flush_frame().unwrap();
+        let hit = writer.hit_test(1, 2).unwrap();
+        assert_eq!(hit.id, Some(WidgetId(2)));
+        assert_eq!((hit.x, hit.y), (0, 0));
+        let hit = writer.hit_test(3, 1).unwrap();
+        assert_eq!(hit.id, Some(WidgetId(1)));
+        assert_eq!((hit.x, hit.y), (2, 1));
+        //the map is replaced on every frame
+        writer.flush_frame().unwrap();
+        assert!(writer.last_hit_map().is_empty());
+    }
+
+    #[test]
+    fn test_suspend_repaints_everything() {
+        let mut writer: TW = TerminalWriter::new(3, 1, Vec::new());
+        writer.write_text_line("ab", 0, 0);
+        writer.flush_frame().unwrap();
+        writer.suspend().unwrap().resume().unwrap();
+        {
+            let _guard = writer.suspend().unwrap();
+        }
+        writer.stream_mut().clear();
+        writer.write_text_line("ab", 0, 0);
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(output, "\x1b[0m\x1b[2J\x1b[1;1H\x1b[38;5;15;48;5;0mab ");
+    }
+
+    #[test]
+    fn test_flush_frame_attributes() {
+        let mut writer: TW = TerminalWriter::new(10, 1, Vec::new());
+        writer.write_text_line("abcd", 0, 0);
+        let bold = TextAttributes::from(Attribute::Bold);
+        writer.write_attributes(
+            &[
+                bold.with(Attribute::Underlined),
+                bold,
+                bold.with(Attribute::Italic),
+                TextAttributes::default(),
+            ],
+            Rect::new(0, 0, 4, 1),
+        );
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(
+            output,
+            "\x1b[1;1H\x1b[1m\x1b[4m\x1b[38;5;15;48;5;0ma\x1b[24mb\x1b[3mc\x1b[22m\x1b[23md"
+        );
+    }
+
+    #[test]
+    fn test_attributes_persist_between_frames() {
+        let mut writer: TW = TerminalWriter::new(2, 1, Vec::new());
+        writer.attributes_fill(Attribute::Dim.into(), None);
+        writer.flush_frame().unwrap();
+        writer.stream_mut().clear();
+        // the attributes are still active so only the changed simble is written
+        writer.attributes_fill(Attribute::Dim.into(), None);
+        writer.write_text_line("x", 1, 0);
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(output, "\x1b[1;2H\x1b[38;5;15;48;5;0mx");
+    }
+
+    #[test]
+    fn test_flush_frame_wide_simbles() {
+        let mut writer: TW = TerminalWriter::new(6, 1, Vec::new());
+        writer.write_text_line("中a文", 0, 0);
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(output, "\x1b[1;1H\x1b[38;5;15;48;5;0m中a文");
+        writer.stream_mut().clear();
+        // changing the color of the right half redraws the whole simble
+        writer.write_text_line("中a文", 0, 0);
+        writer.background_fill(BackgroundColor(Color::Blue), Some(Rect::new(1, 0, 1, 1)));
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(output, "\x1b[1;1H\x1b[38;5;15;48;5;0m中");
+    }
+
+    #[test]
+    fn test_flush_frame_broken_wide_simbles() {
+        let mut writer: TW = TerminalWriter::new(4, 1, Vec::new());
+        // the right half of the first simble is overwritten and the last one is clipped
+        writer.write_text_line("中", 0, 0);
+        writer.write_text_line("b", 1, 0);
+        writer.write_text_line("文", 3, 0);
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(output, "\x1b[1;1H\x1b[38;5;15;48;5;0m b\x1b[1;4H ");
+    }
+
+    #[test]
+    fn test_synchronized_output() {
+        let mut writer: TW = TerminalWriter::new(10, 3, Vec::new());
+        assert!(!writer.uses_synchronized_output());
+        writer.set_synchronized_output(SynchronizedOutput::Always);
+        assert!(writer.uses_synchronized_output());
+        writer.flush_frame().unwrap();
+        // an empty frame is not wrapped
+        assert!(writer.stream().is_empty());
+        writer.write_text_line("a", 0, 0);
+        writer.flush_frame().unwrap();
+        let output = String::from_utf8(writer.stream().clone()).unwrap();
+        assert_eq!(
+            output,
+            "\x1b[?2026h\x1b[1;1H\x1b[38;5;15;48;5;0ma\x1b[?2026l"
+        );
+    }
+
+    #[test]
+    fn test_synchronized_output_auto_needs_tty() {
+        let mut writer: TW = TerminalWriter::new(10, 3, Vec::new());
+        writer.set_synchronized_output(SynchronizedOutput::Auto);
+        assert_eq!(writer.synchronized_output(), SynchronizedOutput::Auto);
+        assert!(!writer.uses_synchronized_output());
     }
 
     #[test]
     fn test_resize() {
-        let mut writer: TW = TerminalWriter::new(80, 25, stdout());
-        writer.resize(100, 30);
+        let mut writer: TW = TerminalWriter::new(80, 25, Vec::new());
+        writer.resize(100, 30).unwrap();
         assert_eq!(writer.background.area(), Rect::new(0, 0, 100, 30));
         assert_eq!(writer.foreground.area(), Rect::new(0, 0, 100, 30));
         assert_eq!(writer.text.area(), Rect::new(0, 0, 100, 30));
@@ -235,34 +1161,38 @@ mod tests {
 
     #[test]
     fn test_drop() {
-        let writer: TW = TerminalWriter::new(80, 25, stdout());
-        // Expect that raw mode is disabled and the alternate screen is left when dropped
+        let writer: TW = TerminalWriter::new(80, 25, Vec::new());
+        // the terminal was never prepared so dropping must not try to restore it
+        drop(writer);
     }
 
     #[test]
     fn test_background_fill() {
-        let mut writer: TW = TerminalWriter::new(80, 25, stdout());
+        let mut writer: TW = TerminalWriter::new(80, 25, Vec::new());
         writer.background_fill(BackgroundColor(Color::Blue), None);
-        // Expect the background buffer to be filled with the specified color
+        assert!(writer.background[(0, 0)] == BackgroundColor(Color::Blue));
+        assert!(writer.background[(79, 24)] == BackgroundColor(Color::Blue));
     }
 
     #[test]
     fn test_foreground_fill() {
-        let mut writer: TW = TerminalWriter::new(80, 25, stdout());
+        let mut writer: TW = TerminalWriter::new(80, 25, Vec::new());
         writer.foreground_fill(ForegroundColor(Color::Blue), None);
-        // Expect the foreground buffer to be filled with the specified color
+        assert!(writer.foreground[(0, 0)] == ForegroundColor(Color::Blue));
+        assert!(writer.foreground[(79, 24)] == ForegroundColor(Color::Blue));
     }
 
     #[test]
     fn test_simble_fill() {
-        let mut writer: TW = TerminalWriter::new(80, 25, stdout());
+        let mut writer: TW = TerminalWriter::new(80, 25, Vec::new());
         writer.simble_fill(Simble('A'.to_compact_string()), None);
-        // Expect the text buffer to be filled with the specified simble
+        assert_eq!(writer.text[(0, 0)], Simble('A'.to_compact_string()));
+        assert_eq!(writer.text[(79, 24)], Simble('A'.to_compact_string()));
     }
 
     #[test]
     fn test_write_simbles() {
-        let mut writer: TW = TerminalWriter::new(80, 25, stdout());
+        let mut writer: TW = TerminalWriter::new(80, 25, Vec::new());
         let text = vec![
             Simble('H'.to_compact_string()),
             Simble('e'.to_compact_string()),
@@ -271,12 +1201,15 @@ mod tests {
             Simble('o'.to_compact_string()),
         ];
         writer.write_simbles(&text, Rect::new(0, 0, 5, 1));
-        // Expect the text buffer to contain "Hello"
+        for (x, simble) in text.iter().enumerate() {
+            assert_eq!(&writer.text[(x as u16, 0)], simble);
+        }
+        assert_eq!(writer.text[(5, 0)], Simble::default());
     }
 
     #[test]
     fn test_area() {
-        let writer: TW = TerminalWriter::new(80, 25, stdout());
+        let writer: TW = TerminalWriter::new(80, 25, Vec::new());
         assert_eq!(writer.area(), Rect::new(0, 0, 80, 25));
     }
 
@@ -285,13 +1218,18 @@ mod tests {
         struct MockWidget;
         impl Widget<TW> for MockWidget {
             fn render_widget(&self, painter: &mut TW) {
-                // Mock widget rendering logic
+                painter.simble_fill(Simble('#'.to_compact_string()), None);
             }
         }
 
-        let mut writer = TerminalWriter::new(80, 25, stdout());
+        let mut writer = TerminalWriter::new(80, 25, Vec::new());
         let widget = MockWidget;
-        writer.render_widget(&widget, Rect::new(0, 0, 10, 5), 0, 0);
-        // Expect the widget to be rendered within the specified area
+        writer.render_widget(&widget, Rect::new(2, 1, 10, 5), 0, 0);
+        // the fill must stay inside the area of the widget
+        assert_eq!(writer.text[(2, 1)], Simble('#'.to_compact_string()));
+        assert_eq!(writer.text[(11, 5)], Simble('#'.to_compact_string()));
+        assert_eq!(writer.text[(1, 1)], Simble::default());
+        assert_eq!(writer.text[(12, 5)], Simble::default());
+        assert_eq!(writer.text[(2, 6)], Simble::default());
     }
 }
@@ -0,0 +1,366 @@
+use super::{
+    buffer::{Buffer, VecBuffer},
+    buffer_mediator::BufferMediator,
+    hit_test::{Hit, HitMap},
+    painter::Painter,
+    rect::Rect,
+    widget::{StatefulWidget, Widget},
+    BackgroundColor, ForegroundColor, Simble, TextAttributes,
+};
+use crossterm::{
+    style::{Colors, Print, ResetColor, SetAttribute, SetAttributes, SetColors},
+    QueueableCommand,
+};
+use std::fmt::Write as _;
+
+//a single cell of the rendered frame
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cell {
+    pub background: BackgroundColor,
+    pub foreground: ForegroundColor,
+    pub simble: Simble,
+    pub attributes: TextAttributes,
+}
+
+//headless painter that keeps the painted frame around so it can be inspected by tests
+pub struct TestBackend<
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes> = VecBuffer<TextAttributes>,
+> {
+    background: A,
+    foreground: B,
+    text: C,
+    attributes: D,
+    mediator_stack: Vec<BufferMediator>,
+    hit_map: HitMap,
+}
+impl<A, B, C, D> TestBackend<A, B, C, D>
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+{
+    pub fn new(width: u16, height: u16) -> TestBackend<A, B, C, D> {
+        TestBackend {
+            background: A::new(width, height),
+            foreground: B::new(width, height),
+            text: C::new(width, height),
+            attributes: D::new(width, height),
+            mediator_stack: Vec::new(),
+            hit_map: HitMap::new(),
+        }
+    }
+    pub fn reset(&mut self) {
+        self.background.reset();
+        self.foreground.reset();
+        self.text.reset();
+        self.attributes.reset();
+        self.hit_map.clear();
+    }
+    pub fn resize(&mut self, width: u16, height: u16) {
+        self.background.resize(width, height);
+        self.foreground.resize(width, height);
+        self.text.resize(width, height);
+        self.attributes.resize(width, height);
+    }
+    //widgets rendered since the last reset
+    pub fn hit_map(&self) -> &HitMap {
+        &self.hit_map
+    }
+    pub fn hit_test(&self, x: u16, y: u16) -> Option<Hit> {
+        self.hit_map.hit_test(x, y)
+    }
+    pub fn frame_area(&self) -> Rect {
+        self.text.area()
+    }
+    pub fn cell(&self, x: u16, y: u16) -> Cell {
+        Cell {
+            background: self.background[(x, y)],
+            foreground: self.foreground[(x, y)],
+            simble: self.text[(x, y)].clone(),
+            attributes: self.attributes[(x, y)],
+        }
+    }
+    pub fn row(&self, y: u16) -> String {
+        let area = self.text.area();
+        self.region_text(Rect::new(0, y, area.width, 1))
+            .pop()
+            .unwrap_or_default()
+    }
+    //text of every row of the area, clipped to the frame
+    pub fn region_text(&self, area: Rect) -> Vec<String> {
+        let area = self.text.area().crop(&area);
+        (area.top()..area.bottom())
+            .map(|y| {
+                (area.left()..area.right())
+                    .map(|x| self.text[(x, y)].0.as_str())
+                    .collect()
+            })
+            .collect()
+    }
+    //the whole frame as plain text, one line per row
+    pub fn to_text(&self) -> String {
+        self.region_text(self.text.area()).join("\n")
+    }
+    //the whole frame as text with ansi color sequences, useful to print a frame from a test
+    pub fn to_styled_string(&self) -> String {
+        let area = self.text.area();
+        let mut out = Vec::new();
+        for y in 0..area.height {
+            for x in 0..area.width {
+                let cell = self.cell(x, y);
+                out.queue(SetAttribute(crossterm::style::Attribute::Reset))
+                    .and_then(|out| out.queue(SetAttributes(cell.attributes.0)))
+                    .and_then(|out| {
+                        out.queue(SetColors(Colors::new(cell.foreground.0, cell.background.0)))
+                    })
+                    .and_then(|out| out.queue(Print(cell.simble.0)))
+                    .expect("writing to a vec can't fail");
+            }
+            out.queue(ResetColor)
+                .and_then(|out| out.queue(Print('\n')))
+                .expect("writing to a vec can't fail");
+        }
+        String::from_utf8(out).expect("escape sequences are valid utf8")
+    }
+    pub fn assert_cell(&self, x: u16, y: u16, expected: &Cell) {
+        let cell = self.cell(x, y);
+        assert_eq!(
+            &cell,
+            expected,
+            "cell ({}, {}) differs, frame:\n{}",
+            x,
+            y,
+            self.to_text()
+        );
+    }
+    pub fn assert_simble(&self, x: u16, y: u16, expected: &str) {
+        let cell = self.cell(x, y);
+        assert_eq!(
+            cell.simble.0.as_str(),
+            expected,
+            "simble at ({}, {}) differs, frame:\n{}",
+            x,
+            y,
+            self.to_text()
+        );
+    }
+    pub fn assert_row(&self, y: u16, expected: &str) {
+        assert_eq!(
+            self.row(y),
+            expected,
+            "row {} differs, frame:\n{}",
+            y,
+            self.to_text()
+        );
+    }
+    pub fn assert_region(&self, area: Rect, expected: &[&str]) {
+        let actual = self.region_text(area);
+        if actual != expected {
+            let mut message = String::new();
+            writeln!(message, "region {:?} differs", area).unwrap();
+            writeln!(message, "expected:").unwrap();
+            for line in expected {
+                writeln!(message, "|{}|", line).unwrap();
+            }
+            writeln!(message, "actual:").unwrap();
+            for line in actual.iter() {
+                writeln!(message, "|{}|", line).unwrap();
+            }
+            panic!("{}", message);
+        }
+    }
+    pub fn assert_text(&self, expected: &[&str]) {
+        self.assert_region(self.text.area(), expected);
+    }
+    fn mediator(&self) -> BufferMediator {
+        *self
+            .mediator_stack
+            .last()
+            .unwrap_or(&BufferMediator::new(self.text.area(), 0, 0))
+    }
+    fn fill<T: Default + Sized + Clone + Send + Sync>(
+        mediator: &BufferMediator,
+        content: T,
+        area: Option<Rect>,
+        buff: &mut impl Buffer<T>,
+    ) {
+        let area = area.unwrap_or(buff.area());
+        let fill = vec![content; area.height as usize * area.width as usize];
+        mediator.write(fill.as_slice(), area, buff);
+    }
+}
+impl<A, B, C, D> Painter for TestBackend<A, B, C, D>
+where
+    A: Buffer<BackgroundColor>,
+    B: Buffer<ForegroundColor>,
+    C: Buffer<Simble>,
+    D: Buffer<TextAttributes>,
+{
+    fn background_fill(&mut self, color: BackgroundColor, area: Option<Rect>) {
+        Self::fill(&self.mediator(), color, area, &mut self.background);
+    }
+
+    fn foreground_fill(&mut self, color: ForegroundColor, area: Option<Rect>) {
+        Self::fill(&self.mediator(), color, area, &mut self.foreground);
+    }
+
+    fn simble_fill(&mut self, color: Simble, area: Option<Rect>) {
+        Self::fill(&self.mediator(), color, area, &mut self.text);
+    }
+
+    fn attributes_fill(&mut self, attributes: TextAttributes, area: Option<Rect>) {
+        Self::fill(&self.mediator(), attributes, area, &mut self.attributes);
+    }
+
+    fn write_simbles(&mut self, text: &[Simble], area: Rect) {
+        self.mediator().write(text, area, &mut self.text);
+    }
+
+    fn write_background_color(&mut self, color: &[BackgroundColor], area: Rect) {
+        self.mediator().write(color, area, &mut self.background);
+    }
+
+    fn write_foreground_color(&mut self, color: &[ForegroundColor], area: Rect) {
+        self.mediator().write(color, area, &mut self.foreground);
+    }
+
+    fn write_attributes(&mut self, attributes: &[TextAttributes], area: Rect) {
+        self.mediator()
+            .write(attributes, area, &mut self.attributes);
+    }
+
+    fn area(&self) -> Rect {
+        self.mediator().size()
+    }
+
+    fn render_widget(
+        &mut self,
+        widget: &dyn Widget<TestBackend<A, B, C, D>>,
+        area: Rect,
+        scroll_x: u16,
+        scroll_y: u16,
+    ) {
+        let inner = self.mediator().generate_inner(&area, scroll_x, scroll_y);
+        self.hit_map.enter(widget.id(), area, inner);
+        self.mediator_stack.push(inner);
+        widget.render_widget(self);
+        self.mediator_stack.pop();
+        self.hit_map.leave();
+    }
+
+    fn render_stateful_widget<T: StatefulWidget<Self> + ?Sized>(
+        &mut self,
+        widget: &T,
+        state: &mut T::State,
+        area: Rect,
+        scroll_x: u16,
+        scroll_y: u16,
+    ) {
+        let inner = self.mediator().generate_inner(&area, scroll_x, scroll_y);
+        self.hit_map.enter(widget.id(), area, inner);
+        self.mediator_stack.push(inner);
+        widget.render_stateful_widget(self, state);
+        self.mediator_stack.pop();
+        self.hit_map.leave();
+    }
+}
+//the backend most widget tests render in
+#[cfg(test)]
+pub(crate) type TB =
+    TestBackend<VecBuffer<BackgroundColor>, VecBuffer<ForegroundColor>, VecBuffer<Simble>>;
+//a left button press at the cell, as the terminal reports it
+#[cfg(test)]
+pub(crate) fn left_click(column: u16, row: u16) -> super::input::InputEvent {
+    use crossterm::event::{KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
+    super::input::InputEvent::Mouse(MouseEvent {
+        kind: MouseEventKind::Down(MouseButton::Left),
+        column,
+        row,
+        modifiers: KeyModifiers::NONE,
+    })
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::renderer::painter::TextPainer;
+    use crossterm::style::{Attribute, Color};
+
+    struct Label;
+    impl<P: Painter + TextPainer> Widget<P> for Label {
+        fn render_widget(&self, painter: &mut P) {
+            painter.write_text_line("label", 0, 0);
+        }
+    }
+
+    #[test]
+    fn test_new() {
+        let backend = TB::new(4, 2);
+        backend.assert_text(&["    ", "    "]);
+        assert_eq!(backend.cell(0, 0), Cell::default());
+    }
+
+    #[test]
+    fn test_write_text_line() {
+        let mut backend = TB::new(10, 2);
+        backend.write_text_line("Hello", 2, 1);
+        backend.assert_row(1, "  Hello   ");
+        backend.assert_simble(2, 1, "H");
+        backend.assert_region(Rect::new(2, 0, 5, 2), &["     ", "Hello"]);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut backend = TB::new(4, 2);
+        backend.background_fill(Color::Blue.into(), Some(Rect::new(1, 0, 2, 1)));
+        backend.foreground_fill(Color::Red.into(), None);
+        backend.simble_fill('x'.into(), Some(Rect::new(0, 1, 4, 1)));
+        backend.assert_text(&["    ", "xxxx"]);
+        backend.assert_cell(
+            1,
+            0,
+            &Cell {
+                background: Color::Blue.into(),
+                foreground: Color::Red.into(),
+                simble: Simble::default(),
+                attributes: TextAttributes::default(),
+            },
+        );
+        assert_eq!(backend.cell(0, 0).background, BackgroundColor::default());
+    }
+
+    #[test]
+    fn test_render_widget_clips() {
+        let mut backend = TB::new(10, 2);
+        backend.render_widget(&Label, Rect::new(1, 0, 3, 1), 0, 0);
+        backend.render_widget(&Label, Rect::new(5, 1, 5, 1), 2, 0);
+        backend.assert_text(&[" lab      ", "     bel  "]);
+    }
+
+    #[test]
+    fn test_region_text_is_clipped() {
+        let backend = TB::new(3, 1);
+        assert_eq!(backend.region_text(Rect::new(1, 0, 10, 10)), vec!["  "]);
+    }
+
+    #[test]
+    fn test_to_styled_string() {
+        let mut backend = TB::new(1, 1);
+        backend.simble_fill('a'.into(), None);
+        backend.attributes_fill(Attribute::Bold.into(), None);
+        assert_eq!(
+            backend.to_styled_string(),
+            "\x1b[0m\x1b[1m\x1b[38;5;15;48;5;0ma\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_row_mismatch() {
+        let backend = TB::new(3, 1);
+        backend.assert_row(0, "abc");
+    }
+}
@@ -0,0 +1,189 @@
+use super::{painter::Painter, rect::Rect, BackgroundColor, ForegroundColor, TextAttributes};
+use compact_str::CompactString;
+use crossterm::style::{Attribute, Color};
+
+//colors and attributes of a span, None leaves what is already painted under the text
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Style {
+    pub foreground: Option<ForegroundColor>,
+    pub background: Option<BackgroundColor>,
+    pub attributes: Option<TextAttributes>,
+}
+impl Style {
+    pub const fn new() -> Self {
+        Self {
+            foreground: None,
+            background: None,
+            attributes: None,
+        }
+    }
+    pub fn fg(mut self, color: Color) -> Self {
+        self.foreground = Some(color.into());
+        self
+    }
+    pub fn bg(mut self, color: Color) -> Self {
+        self.background = Some(color.into());
+        self
+    }
+    pub fn add_attribute(mut self, attribute: Attribute) -> Self {
+        self.attributes = Some(self.attributes.unwrap_or_default().with(attribute));
+        self
+    }
+    //fills the area, or the whole painter, with the parts of the style that are set
+    pub fn fill<P: Painter>(&self, painter: &mut P, area: Option<Rect>) {
+        if let Some(color) = self.background {
+            painter.background_fill(color, area);
+        }
+        if let Some(color) = self.foreground {
+            painter.foreground_fill(color, area);
+        }
+        if let Some(attributes) = self.attributes {
+            painter.attributes_fill(attributes, area);
+        }
+    }
+    //values set in other win over the ones in self
+    pub fn patch(self, other: Style) -> Self {
+        Self {
+            foreground: other.foreground.or(self.foreground),
+            background: other.background.or(self.background),
+            attributes: other.attributes.or(self.attributes),
+        }
+    }
+}
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+    pub content: CompactString,
+    pub style: Style,
+}
+impl Span {
+    pub fn raw(content: impl Into<CompactString>) -> Self {
+        Self {
+            content: content.into(),
+            style: Style::default(),
+        }
+    }
+    pub fn styled(content: impl Into<CompactString>, style: Style) -> Self {
+        Self {
+            content: content.into(),
+            style,
+        }
+    }
+}
+impl From<&str> for Span {
+    fn from(content: &str) -> Self {
+        Self::raw(content)
+    }
+}
+impl From<String> for Span {
+    fn from(content: String) -> Self {
+        Self::raw(content)
+    }
+}
+//a single line of text, newlines inside spans are not interpreted
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Line {
+    pub spans: Vec<Span>,
+}
+impl Line {
+    pub fn new(spans: Vec<Span>) -> Self {
+        Self { spans }
+    }
+    //the style is applied under the style of every span
+    pub fn styled(mut self, style: Style) -> Self {
+        for span in self.spans.iter_mut() {
+            span.style = style.patch(span.style);
+        }
+        self
+    }
+    pub fn push(&mut self, span: impl Into<Span>) {
+        self.spans.push(span.into());
+    }
+}
+impl From<&str> for Line {
+    fn from(content: &str) -> Self {
+        Self::new(vec![Span::raw(content)])
+    }
+}
+impl From<Span> for Line {
+    fn from(span: Span) -> Self {
+        Self::new(vec![span])
+    }
+}
+impl From<Vec<Span>> for Line {
+    fn from(spans: Vec<Span>) -> Self {
+        Self::new(spans)
+    }
+}
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Text {
+    pub lines: Vec<Line>,
+}
+impl Text {
+    pub fn new(lines: Vec<Line>) -> Self {
+        Self { lines }
+    }
+    pub fn push(&mut self, line: impl Into<Line>) {
+        self.lines.push(line.into());
+    }
+}
+impl From<&str> for Text {
+    fn from(content: &str) -> Self {
+        Self::new(content.lines().map(Line::from).collect())
+    }
+}
+impl From<String> for Text {
+    fn from(content: String) -> Self {
+        Self::from(content.as_str())
+    }
+}
+impl From<Line> for Text {
+    fn from(line: Line) -> Self {
+        Self::new(vec![line])
+    }
+}
+impl From<Span> for Text {
+    fn from(span: Span) -> Self {
+        Self::new(vec![span.into()])
+    }
+}
+impl From<Vec<Line>> for Text {
+    fn from(lines: Vec<Line>) -> Self {
+        Self::new(lines)
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_from_str() {
+        let text = Text::from("a\nb");
+        assert_eq!(text.lines, vec![Line::from("a"), Line::from("b")]);
+    }
+
+    #[test]
+    fn test_line_style_is_patched_under_spans() {
+        let line = Line::from(vec![
+            Span::raw("[q]"),
+            Span::styled(" quit", Style::new().fg(Color::Red)),
+        ])
+        .styled(Style::new().fg(Color::Yellow).bg(Color::Blue));
+        assert_eq!(
+            line.spans[0].style,
+            Style::new().fg(Color::Yellow).bg(Color::Blue)
+        );
+        assert_eq!(
+            line.spans[1].style,
+            Style::new().fg(Color::Red).bg(Color::Blue)
+        );
+    }
+
+    #[test]
+    fn test_add_attribute() {
+        let style = Style::new()
+            .add_attribute(Attribute::Bold)
+            .add_attribute(Attribute::Italic);
+        let attributes = style.attributes.unwrap();
+        assert!(attributes.has(Attribute::Bold) && attributes.has(Attribute::Italic));
+    }
+}
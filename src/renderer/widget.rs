@@ -1,7 +1,20 @@
-use super::painter::Painter;
+use super::{hit_test::WidgetId, painter::Painter};
 
 pub trait Widget<P: Painter>: Sync + Send {
     fn render_widget(&self, painter: &mut P);
+    //identifies the widget in hit tests, widgets without one are still hit
+    fn id(&self) -> Option<WidgetId> {
+        None
+    }
+}
+//a widget rendered with state owned by the application, the render can update the state
+//like clamping a selection to the items that exist
+pub trait StatefulWidget<P: Painter>: Sync + Send {
+    type State;
+    fn render_stateful_widget(&self, painter: &mut P, state: &mut Self::State);
+    fn id(&self) -> Option<WidgetId> {
+        None
+    }
 }
 
 /*impl<P: Painter, T: Widget<P>> Widget<P> for Box<T> {